@@ -0,0 +1,497 @@
+use proc_macro2::TokenStream;
+use quote::{quote_spanned, ToTokens};
+use syn::{
+    meta::ParseNestedMeta, spanned::Spanned, token, Attribute, Expr, Ident, LitStr, Path,
+    Visibility,
+};
+
+use crate::case::RenameRule;
+
+/// The name of the attribute every derive of this crate is configured with
+const ATTRIBUTE: &str = "struct_tools";
+
+/**
+Options of the deriving struct, given as `#[struct_tools(...)]`
+
+```text
+#[struct_tools(fields, values, into)]
+#[struct_tools(enum_derive(Debug), mut_enum_derive(Debug), builder_derive(Debug))]
+#[struct_tools(rename_all = "camelCase", vis = "pub(crate)", crate = "struct_tools")]
+#[struct_tools(enum_vis = "pub", builder_vis = "pub(super)")]
+#[struct_tools(flatten_separator = "/")]
+#[struct_tools(field_enum = "BookChange", builder = "BookDraft", enum_method = "as_values")]
+#[struct_tools(value_enum = crate::Value)]
+#[struct_tools(patch = "BookUpdate", patch_derive(Debug, Clone))]
+```
+
+The marker attributes used before (`#[StructFields]`, `#[EnumDerive(Debug)]`, ...) are still
+understood, but produce a deprecation warning.
+*/
+#[derive(Default)]
+pub(crate) struct ContainerAttrs {
+    /// `fields`: generate the functions listing the names of the fields
+    pub(crate) fields: bool,
+    /// `values`: generate the methods listing the values of the fields
+    pub(crate) values: bool,
+    /// `into`: generate the methods moving the fields out of the struct, which can't be used by a
+    /// struct implementing `Drop`
+    pub(crate) into: bool,
+    /// `enum_derive(...)`: traits derived for the generated enums
+    pub(crate) enum_derive: Vec<Path>,
+    /// `mut_enum_derive(...)`: traits derived for the generated enums holding `&mut` references
+    pub(crate) mut_enum_derive: Vec<Path>,
+    /// `ref_enum_derive(...)`: traits derived for the generated enums holding `&` references
+    pub(crate) ref_enum_derive: Vec<Path>,
+    /// `builder_derive(...)`: traits derived for the generated builder
+    pub(crate) builder_derive: Vec<Path>,
+    /// `patch_derive(...)`: traits derived for the generated patch
+    pub(crate) patch_derive: Vec<Path>,
+    /// `rename_all = "..."`: the naming convention the fields are reported in
+    pub(crate) rename_all: Option<RenameRule>,
+    /// `vis = "..."`: the visibility of the generated items, instead of the one of the struct
+    pub(crate) vis: Option<Visibility>,
+    /// `enum_vis = "..."`: the visibility of the enums of `StructEnum`, `StructEnumMut` and
+    /// `StructEnumRef` and their error, instead of `vis`
+    pub(crate) enum_vis: Option<Visibility>,
+    /// `field_enum_vis = "..."`: the visibility of the enums of `StructFieldEnum`,
+    /// `StructFieldEnumMut` and `StructFieldEnumRef` and their helper types, instead of `vis`
+    pub(crate) field_enum_vis: Option<Visibility>,
+    /// `builder_vis = "..."`: the visibility of the builders and their helper types, instead of `vis`
+    pub(crate) builder_vis: Option<Visibility>,
+    /// `patch_vis = "..."`: the visibility of the struct generated by `StructPatch`, instead of `vis`
+    pub(crate) patch_vis: Option<Visibility>,
+    /// `enum = "..."`: the name of the enum generated by `StructEnum`
+    pub(crate) type_enum: Option<Ident>,
+    /// `value_enum = path::to::Enum`: an existing enum `StructEnum` wraps the fields in, instead of
+    /// generating one
+    pub(crate) value_enum: Option<Path>,
+    /// `enum_mut = "..."`: the name of the enum generated by `StructEnumMut`
+    pub(crate) type_enum_mut: Option<Ident>,
+    /// `enum_ref = "..."`: the name of the enum generated by `StructEnumRef`
+    pub(crate) type_enum_ref: Option<Ident>,
+    /// `field_enum = "..."`: the name of the enum generated by `StructFieldEnum`
+    pub(crate) field_enum: Option<Ident>,
+    /// `field_enum_mut = "..."`: the name of the enum generated by `StructFieldEnumMut`
+    pub(crate) field_enum_mut: Option<Ident>,
+    /// `field_enum_ref = "..."`: the name of the enum generated by `StructFieldEnumRef`
+    pub(crate) field_enum_ref: Option<Ident>,
+    /// `builder = "..."`: the name of the generated builder
+    pub(crate) builder: Option<Ident>,
+    /// `builder_error = "..."`: the name of the error returned by the builder of `StructBuilderOld`
+    pub(crate) builder_error: Option<Ident>,
+    /// `patch = "..."`: the name of the struct generated by `StructPatch`
+    pub(crate) patch: Option<Ident>,
+    /// `field_error = "..."`: the name of the error returned when setting a field by its name
+    pub(crate) field_error: Option<Ident>,
+    /// `from_values_error = "..."`: the name of the error returned when building the struct from
+    /// its values
+    pub(crate) from_values_error: Option<Ident>,
+    /// `enum_error = "..."`: the name of the error returned when converting the enum of
+    /// `StructEnum` into the type of another variant
+    pub(crate) enum_error: Option<Ident>,
+    /// `enum_mut_error = "..."`: the name of the error returned when converting the enum of
+    /// `StructEnumMut` into the type of another variant
+    pub(crate) enum_mut_error: Option<Ident>,
+    /// `field_change = "..."`: the name of the changes returned by `diff`
+    pub(crate) field_change: Option<Ident>,
+    /// `enum_method = "..."`: the name of the method returning the fields as `StructEnum`
+    pub(crate) enum_method: Option<Ident>,
+    /// `enum_mut_method = "..."`: the name of the method returning the fields as `StructEnumMut`
+    pub(crate) enum_mut_method: Option<Ident>,
+    /// `enum_ref_method = "..."`: the name of the method returning the fields as `StructEnumRef`
+    pub(crate) enum_ref_method: Option<Ident>,
+    /// `field_enum_method = "..."`: the name of the method returning the fields as `StructFieldEnum`
+    pub(crate) field_enum_method: Option<Ident>,
+    /// `field_enum_mut_method = "..."`: the name of the method returning the fields as
+    /// `StructFieldEnumMut`
+    pub(crate) field_enum_mut_method: Option<Ident>,
+    /// `field_enum_ref_method = "..."`: the name of the method returning the fields as
+    /// `StructFieldEnumRef`
+    pub(crate) field_enum_ref_method: Option<Ident>,
+    /// `flatten_separator = "..."`: the separator between the name of a flattened field and the
+    /// names of its own fields, `"."` by default
+    pub(crate) flatten_separator: Option<LitStr>,
+    /// `crate = "..."`: the path of the `struct-tools` crate, whose traits are only implemented when
+    /// it is given
+    pub(crate) krate: Option<Path>,
+    /// Every deprecated attribute that was used
+    pub(crate) deprecations: Vec<Deprecation>,
+}
+
+impl ContainerAttrs {
+    /// The visibility of a generated item, the one given for it, `vis` or else `inherited` from the struct
+    pub(crate) fn item_vis<'a>(
+        &'a self,
+        item: &'a Option<Visibility>,
+        inherited: &'a Visibility,
+    ) -> &'a Visibility {
+        item.as_ref().or(self.vis.as_ref()).unwrap_or(inherited)
+    }
+
+    pub(crate) fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut result = Self::default();
+        for attr in attrs {
+            let path = attr.path();
+            if path.is_ident(ATTRIBUTE) {
+                attr.parse_nested_meta(|meta| result.parse_meta(meta))?;
+            } else if path.is_ident("StructFields") {
+                attr.meta.require_path_only()?;
+                result.fields = true;
+                result.deprecations.push(Deprecation::new(attr, "fields"));
+            } else if path.is_ident("StructValues") {
+                attr.meta.require_path_only()?;
+                result.values = true;
+                result.deprecations.push(Deprecation::new(attr, "values"));
+            } else if path.is_ident("EnumDerive") {
+                attr.parse_nested_meta(|meta| push_path(&mut result.enum_derive, meta))?;
+                result
+                    .deprecations
+                    .push(Deprecation::new(attr, "enum_derive(...)"));
+            } else if path.is_ident("MutEnumDerive") {
+                attr.parse_nested_meta(|meta| push_path(&mut result.mut_enum_derive, meta))?;
+                result
+                    .deprecations
+                    .push(Deprecation::new(attr, "mut_enum_derive(...)"));
+            } else if path.is_ident("BuilderDerive") {
+                attr.parse_nested_meta(|meta| push_path(&mut result.builder_derive, meta))?;
+                result
+                    .deprecations
+                    .push(Deprecation::new(attr, "builder_derive(...)"));
+            }
+        }
+        Ok(result)
+    }
+
+    fn parse_meta(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("fields") {
+            self.fields = true;
+        } else if meta.path.is_ident("values") {
+            self.values = true;
+        } else if meta.path.is_ident("into") {
+            self.into = true;
+        } else if meta.path.is_ident("enum_derive") {
+            meta.parse_nested_meta(|meta| push_path(&mut self.enum_derive, meta))?;
+        } else if meta.path.is_ident("mut_enum_derive") {
+            meta.parse_nested_meta(|meta| push_path(&mut self.mut_enum_derive, meta))?;
+        } else if meta.path.is_ident("ref_enum_derive") {
+            meta.parse_nested_meta(|meta| push_path(&mut self.ref_enum_derive, meta))?;
+        } else if meta.path.is_ident("builder_derive") {
+            meta.parse_nested_meta(|meta| push_path(&mut self.builder_derive, meta))?;
+        } else if meta.path.is_ident("patch_derive") {
+            meta.parse_nested_meta(|meta| push_path(&mut self.patch_derive, meta))?;
+        } else if meta.path.is_ident("rename_all") {
+            let rule: LitStr = meta.value()?.parse()?;
+            let rule = RenameRule::from_name(&rule.value())
+                .map_err(|message| syn::Error::new(rule.span(), message))?;
+            self.rename_all = Some(rule);
+        } else if meta.path.is_ident("vis") {
+            self.vis = Some(parse_vis(&meta)?);
+        } else if meta.path.is_ident("enum_vis") {
+            self.enum_vis = Some(parse_vis(&meta)?);
+        } else if meta.path.is_ident("field_enum_vis") {
+            self.field_enum_vis = Some(parse_vis(&meta)?);
+        } else if meta.path.is_ident("builder_vis") {
+            self.builder_vis = Some(parse_vis(&meta)?);
+        } else if meta.path.is_ident("patch_vis") {
+            self.patch_vis = Some(parse_vis(&meta)?);
+        } else if meta.path.is_ident("enum") {
+            self.type_enum = Some(parse_name(&meta)?);
+        } else if meta.path.is_ident("value_enum") {
+            let value = meta.value()?;
+            self.value_enum = Some(match value.peek(LitStr) {
+                true => value.parse::<LitStr>()?.parse()?,
+                false => value.parse()?,
+            });
+        } else if meta.path.is_ident("enum_mut") {
+            self.type_enum_mut = Some(parse_name(&meta)?);
+        } else if meta.path.is_ident("enum_ref") {
+            self.type_enum_ref = Some(parse_name(&meta)?);
+        } else if meta.path.is_ident("field_enum") {
+            self.field_enum = Some(parse_name(&meta)?);
+        } else if meta.path.is_ident("field_enum_mut") {
+            self.field_enum_mut = Some(parse_name(&meta)?);
+        } else if meta.path.is_ident("field_enum_ref") {
+            self.field_enum_ref = Some(parse_name(&meta)?);
+        } else if meta.path.is_ident("builder") {
+            self.builder = Some(parse_name(&meta)?);
+        } else if meta.path.is_ident("builder_error") {
+            self.builder_error = Some(parse_name(&meta)?);
+        } else if meta.path.is_ident("patch") {
+            self.patch = Some(parse_name(&meta)?);
+        } else if meta.path.is_ident("field_error") {
+            self.field_error = Some(parse_name(&meta)?);
+        } else if meta.path.is_ident("from_values_error") {
+            self.from_values_error = Some(parse_name(&meta)?);
+        } else if meta.path.is_ident("enum_error") {
+            self.enum_error = Some(parse_name(&meta)?);
+        } else if meta.path.is_ident("enum_mut_error") {
+            self.enum_mut_error = Some(parse_name(&meta)?);
+        } else if meta.path.is_ident("field_change") {
+            self.field_change = Some(parse_name(&meta)?);
+        } else if meta.path.is_ident("enum_method") {
+            self.enum_method = Some(parse_name(&meta)?);
+        } else if meta.path.is_ident("enum_mut_method") {
+            self.enum_mut_method = Some(parse_name(&meta)?);
+        } else if meta.path.is_ident("enum_ref_method") {
+            self.enum_ref_method = Some(parse_name(&meta)?);
+        } else if meta.path.is_ident("field_enum_method") {
+            self.field_enum_method = Some(parse_name(&meta)?);
+        } else if meta.path.is_ident("field_enum_mut_method") {
+            self.field_enum_mut_method = Some(parse_name(&meta)?);
+        } else if meta.path.is_ident("field_enum_ref_method") {
+            self.field_enum_ref_method = Some(parse_name(&meta)?);
+        } else if meta.path.is_ident("flatten_separator") {
+            self.flatten_separator = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("crate") {
+            let path: LitStr = meta.value()?.parse()?;
+            self.krate = Some(path.parse()?);
+        } else {
+            return Err(unknown(&meta));
+        }
+        Ok(())
+    }
+}
+
+/**
+Options of a single field, given as `#[struct_tools(...)]`
+
+```text
+#[struct_tools(builder(default = 0))]
+#[struct_tools(skip)]
+#[struct_tools(skip(iter, enum, builder, from))]
+#[struct_tools(rename = "displayName", alias = "name", alias = "display")]
+#[struct_tools(diff(skip))]
+#[struct_tools(flatten)]
+#[struct_tools(variant = "Pair")]
+#[struct_tools(field_const = "FIELD_TOTAL")]
+```
+
+`#[builder_default(0)]` is still understood, but produces a deprecation warning.
+*/
+#[derive(Default)]
+pub(crate) struct FieldAttrs {
+    /// `builder(default = ...)`: the value the builder starts out with for this field, or fills
+    /// it with if the field is skipped
+    pub(crate) builder_default: Option<Expr>,
+    /// `skip` or `skip(...)`: the derives leaving this field out
+    pub(crate) skip: Skip,
+    /// `rename = "..."`: the name the field is reported as, instead of its identifier
+    pub(crate) rename: Option<LitStr>,
+    /// `alias = "..."`: further names the field is looked up by
+    pub(crate) aliases: Vec<LitStr>,
+    /// `diff(skip)`: leaves the field out of the changes `diff` reports
+    pub(crate) diff_skip: bool,
+    /// `variant = "..."`: the name of the variant holding the type of the field in the enums of
+    /// `StructEnum`, instead of one generated from the type
+    pub(crate) variant: Option<Ident>,
+    /// `flatten`: reports the fields of the field's own struct instead of the field itself
+    pub(crate) flatten: bool,
+    /// `field_const = "..."`: the name of the constant holding the name of the field, instead of
+    /// `FIELD_{FIELDNAME}`
+    pub(crate) field_const: Option<Ident>,
+    /// Every deprecated attribute that was used
+    pub(crate) deprecations: Vec<Deprecation>,
+}
+
+impl FieldAttrs {
+    pub(crate) fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut result = Self::default();
+        for attr in attrs {
+            if attr.path().is_ident(ATTRIBUTE) {
+                attr.parse_nested_meta(|meta| result.parse_meta(meta))?;
+            } else if attr.path().is_ident("builder_default") {
+                let default = attr.parse_args()?;
+                result.set_builder_default(default, attr)?;
+                result
+                    .deprecations
+                    .push(Deprecation::new(attr, "builder(default = ...)"));
+            }
+        }
+        Ok(result)
+    }
+
+    fn parse_meta(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("skip") {
+            self.skip.parse(meta)
+        } else if meta.path.is_ident("rename") {
+            let rename: LitStr = meta.value()?.parse()?;
+            match self.rename.replace(rename) {
+                Some(_) => Err(meta.error("a field can only be renamed once")),
+                None => Ok(()),
+            }
+        } else if meta.path.is_ident("alias") {
+            self.aliases.push(meta.value()?.parse()?);
+            Ok(())
+        } else if meta.path.is_ident("builder") {
+            meta.parse_nested_meta(|meta| {
+                if meta.path.is_ident("default") {
+                    let default = meta.value()?.parse()?;
+                    self.set_builder_default(default, &meta.path)
+                } else {
+                    Err(unknown(&meta))
+                }
+            })
+        } else if meta.path.is_ident("variant") {
+            let variant = parse_name(&meta)?;
+            match self.variant.replace(variant) {
+                Some(_) => Err(meta.error("a field can only name one variant")),
+                None => Ok(()),
+            }
+        } else if meta.path.is_ident("flatten") {
+            self.flatten = true;
+            Ok(())
+        } else if meta.path.is_ident("field_const") {
+            let name = parse_name(&meta)?;
+            match self.field_const.replace(name) {
+                Some(_) => Err(meta.error("a field can only name one constant")),
+                None => Ok(()),
+            }
+        } else if meta.path.is_ident("diff") {
+            meta.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    self.diff_skip = true;
+                    Ok(())
+                } else {
+                    Err(unknown(&meta))
+                }
+            })
+        } else {
+            Err(unknown(&meta))
+        }
+    }
+
+    fn set_builder_default(&mut self, default: Expr, span: impl Spanned) -> syn::Result<()> {
+        match self.builder_default.replace(default) {
+            Some(_) => Err(syn::Error::new(
+                span.span(),
+                "the builder default can only be set once per field",
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+/**
+The derives leaving a field out, `skip` alone meaning all of them
+
+- `iter`: `fields()`, `values()` and `fields_and_values()` of `StructIterTools`
+- `enum`: the enums generated by `StructEnum`, `StructFieldEnum` and their `Mut` counterparts, and
+  the patch of `StructPatch`
+- `builder`: the builders, which fill the field with its `builder(default = ...)` or `Default`
+- `from`: the `From` and `TryInto` impls `StructEnum` generates for the type of the field, as they
+  already exist
+*/
+#[derive(Default, Clone, Copy)]
+pub(crate) struct Skip {
+    pub(crate) iter: bool,
+    pub(crate) enums: bool,
+    pub(crate) builder: bool,
+    pub(crate) from: bool,
+}
+
+impl Skip {
+    fn parse(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+        if !meta.input.peek(token::Paren) {
+            *self = Self {
+                iter: true,
+                enums: true,
+                builder: true,
+                from: true,
+            };
+            return Ok(());
+        }
+        meta.parse_nested_meta(|meta| {
+            if meta.path.is_ident("iter") {
+                self.iter = true;
+            } else if meta.path.is_ident("enum") {
+                self.enums = true;
+            } else if meta.path.is_ident("builder") {
+                self.builder = true;
+            } else if meta.path.is_ident("from") {
+                self.from = true;
+            } else {
+                return Err(unknown(&meta));
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Parses the name of a generated item, given as a string like `field_enum = "BookChange"`
+fn parse_name(meta: &ParseNestedMeta) -> syn::Result<Ident> {
+    let name: LitStr = meta.value()?.parse()?;
+    name.parse().map_err(|_| {
+        syn::Error::new(
+            name.span(),
+            format!("`{}` is not a valid identifier", name.value()),
+        )
+    })
+}
+
+/// Parses the visibility given as the string value of `meta`, like `vis = "pub(crate)"`
+fn parse_vis(meta: &ParseNestedMeta) -> syn::Result<Visibility> {
+    let vis: LitStr = meta.value()?.parse()?;
+    vis.parse()
+}
+
+/// The doc comments among `attrs`, one line each, without the space following `///`
+pub(crate) fn docs(attrs: &[Attribute]) -> String {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            syn::Meta::NameValue(syn::MetaNameValue {
+                value:
+                    Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(doc),
+                        ..
+                    }),
+                ..
+            }) => Some(doc.value()),
+            _ => None,
+        })
+        .flat_map(|doc| {
+            doc.split('\n')
+                .map(|line| line.strip_prefix(' ').unwrap_or(line).to_owned())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn push_path(paths: &mut Vec<Path>, meta: ParseNestedMeta) -> syn::Result<()> {
+    paths.push(meta.path);
+    Ok(())
+}
+
+fn unknown(meta: &ParseNestedMeta) -> syn::Error {
+    let path = meta.path.to_token_stream().to_string().replace(' ', "");
+    meta.error(format!("unknown `{ATTRIBUTE}` attribute `{path}`"))
+}
+
+/// A deprecated attribute that was used
+pub(crate) struct Deprecation {
+    /// The name of the attribute, e.g. `StructFields`
+    pub(crate) attribute: String,
+    /// An item that makes the compiler warn about the use of the attribute
+    pub(crate) warning: TokenStream,
+}
+
+impl Deprecation {
+    fn new(attr: &Attribute, replacement: &str) -> Self {
+        let span = attr.path().span();
+        let name = attr.path();
+        let note = format!("use `#[{ATTRIBUTE}({replacement})]` instead");
+        Self {
+            attribute: name.to_token_stream().to_string(),
+            warning: quote_spanned! {span=>
+                const _: () = {
+                    #[deprecated(note = #note)]
+                    #[allow(non_camel_case_types)]
+                    struct #name;
+                    let _ = #name;
+                };
+            },
+        }
+    }
+}
@@ -0,0 +1,97 @@
+/// A naming convention the fields are renamed to, given as `rename_all = "..."`
+#[derive(Clone, Copy)]
+pub(crate) enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    /// Every rule together with the name it is given as
+    const ALL: [(&'static str, Self); 8] = [
+        ("lowercase", Self::Lower),
+        ("UPPERCASE", Self::Upper),
+        ("PascalCase", Self::Pascal),
+        ("camelCase", Self::Camel),
+        ("snake_case", Self::Snake),
+        ("SCREAMING_SNAKE_CASE", Self::ScreamingSnake),
+        ("kebab-case", Self::Kebab),
+        ("SCREAMING-KEBAB-CASE", Self::ScreamingKebab),
+    ];
+
+    /// Looks up a rule by its name, erroring with the list of known names otherwise
+    pub(crate) fn from_name(name: &str) -> Result<Self, String> {
+        Self::ALL
+            .iter()
+            .find(|(rule_name, _)| *rule_name == name)
+            .map(|(_, rule)| *rule)
+            .ok_or_else(|| {
+                let known = Self::ALL
+                    .iter()
+                    .map(|(rule_name, _)| format!("\"{rule_name}\""))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("unknown `rename_all` rule \"{name}\", expected one of {known}")
+            })
+    }
+
+    /// Renames a field given in snake_case, as field names are by convention
+    pub(crate) fn apply(self, field: &str) -> String {
+        match self {
+            Self::Lower | Self::Snake => field.to_owned(),
+            Self::Upper => field.to_ascii_uppercase(),
+            Self::Pascal => {
+                let mut pascal = String::new();
+                let mut capitalize = true;
+                for chr in field.chars() {
+                    if chr == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(chr.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(chr);
+                    }
+                }
+                pascal
+            }
+            Self::Camel => {
+                let pascal = Self::Pascal.apply(field);
+                let mut chars = pascal.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                    None => pascal,
+                }
+            }
+            Self::ScreamingSnake => field.to_ascii_uppercase(),
+            Self::Kebab => field.replace('_', "-"),
+            Self::ScreamingKebab => Self::ScreamingSnake.apply(field).replace('_', "-"),
+        }
+    }
+}
+
+/// Turns a name given in PascalCase, like the ones of the generated variants, into snake_case
+pub(crate) fn snake_case(pascal: &str) -> String {
+    let chars = pascal.chars().collect::<Vec<_>>();
+    let mut snake = String::new();
+    for (i, chr) in chars.iter().enumerate() {
+        if chr.is_ascii_uppercase() && i > 0 {
+            let previous = chars[i - 1];
+            // an uppercase letter only continues an acronym like the `HTTP` of `HTTPServer`
+            let starts_word = !previous.is_ascii_uppercase()
+                || chars
+                    .get(i + 1)
+                    .is_some_and(|next| next.is_ascii_lowercase());
+            if starts_word && previous != '_' {
+                snake.push('_');
+            }
+        }
+        snake.push(chr.to_ascii_lowercase());
+    }
+    snake
+}
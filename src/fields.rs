@@ -0,0 +1,481 @@
+use std::{collections::HashSet, iter};
+
+use itertools::Itertools;
+use proc_macro2::{Delimiter, Spacing, Span, TokenStream, TokenTree};
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    ext::IdentExt, Data, DataEnum, DataStruct, DataUnion, Field, Fields, Ident, Index, LitByteStr,
+    Member, Type,
+};
+
+use crate::attrs::{ContainerAttrs, FieldAttrs};
+
+/// A field of the deriving struct or of a variant of the deriving enum, no matter if it is named or positional
+pub(crate) struct StructField<'a> {
+    pub(crate) field: &'a Field,
+    /// The variant of the deriving enum the field belongs to, `None` for structs
+    pub(crate) variant: Option<&'a Ident>,
+    /// The position of the field among all fields of the struct or variant, skipped ones included
+    pub(crate) index: usize,
+    /// How the field is accessed, as in `self.#member`
+    pub(crate) member: Member,
+    /// An identifier for bindings and generated items, e.g. `title` or `_0`
+    pub(crate) ident: Ident,
+    /// The name of the field in Rust, e.g. `"title"` or `"0"`
+    pub(crate) name: String,
+    /// The name the field is reported and looked up as, after `rename` and `rename_all`
+    pub(crate) external_name: String,
+    pub(crate) attrs: FieldAttrs,
+}
+
+impl<'a> StructField<'a> {
+    pub(crate) fn ty(&self) -> &'a Type {
+        &self.field.ty
+    }
+
+    /**
+    A pattern matching the bytes of the name the field is reported as and of each of its aliases,
+    usable in a `const fn`
+    */
+    pub(crate) fn name_pattern(&self) -> TokenStream {
+        let aliases = self.attrs.aliases.iter().map(|alias| alias.value());
+        let names = iter::once(self.external_name.clone())
+            .chain(aliases)
+            .map(|name| LitByteStr::new(name.as_bytes(), Span::call_site()));
+        quote!(#(#names)|*)
+    }
+
+    /// What the names of the fields of this field start with when it is flattened, e.g. `"address."`
+    pub(crate) fn flattened_prefixes(&self, separator: &str) -> Vec<String> {
+        let aliases = self.attrs.aliases.iter().map(|alias| alias.value());
+        iter::once(self.external_name.clone())
+            .chain(aliases)
+            .map(|name| name + separator)
+            .collect()
+    }
+
+    /// The variant representing this field in the field enums, e.g. `Title` or `Field0`
+    pub(crate) fn variant_ident(&self) -> syn::Result<Ident> {
+        let name = self.variant_name();
+        parse_ident(&name).ok_or_else(|| {
+            syn::Error::new(
+                self.ident.span(),
+                format!("the generated variant name `{name}` is not a valid identifier"),
+            )
+        })
+    }
+
+    /**
+    The name of the variant representing this field in the field enums, e.g. `Title` or `Field0`,
+    prefixed with the enum variant it belongs to, e.g. `CircleRadius`
+    */
+    pub(crate) fn variant_name(&self) -> String {
+        match self.variant {
+            Some(variant) => variant.unraw().to_string() + &self.field_variant_name(),
+            None => self.field_variant_name(),
+        }
+    }
+
+    fn field_variant_name(&self) -> String {
+        if let Member::Unnamed(index) = &self.member {
+            return format!("Field{}", index.index);
+        }
+        let field = self
+            .name
+            .chars()
+            .enumerate()
+            .map(|(i, chr)| match i {
+                0 => chr.to_uppercase().to_string(),
+                _ => chr.to_string(),
+            })
+            .collect::<String>();
+        let i = field.find('_');
+        let field = match i {
+            Some(i) => remove_underscore(field, i),
+            None => field,
+        };
+        let i = field.find('_');
+        match i {
+            Some(i) => remove_underscore(field, i),
+            None => field,
+        }
+    }
+}
+
+fn remove_underscore(mut field: String, i: usize) -> String {
+    field.remove(i);
+    let mut field = field.chars().map(|chr| chr.to_string()).collect_vec();
+    if field.get(i).is_some() {
+        field[i] = field[i].to_uppercase();
+    }
+    field.join("")
+}
+
+/// The fields of the deriving struct, or those of every variant of the deriving enum
+pub(crate) enum DataFields<'a> {
+    Struct(Vec<StructField<'a>>),
+    Enum(Vec<(&'a Ident, Vec<StructField<'a>>)>),
+}
+
+impl<'a> DataFields<'a> {
+    /// Every field, for enums those of all variants one after another
+    pub(crate) fn all(&self) -> Vec<&StructField<'a>> {
+        match self {
+            Self::Struct(fields) => fields.iter().collect(),
+            Self::Enum(variants) => variants
+                .iter()
+                .flat_map(|(_, fields)| fields.iter())
+                .collect(),
+        }
+    }
+
+    /// Leaves out the fields `skip` returns `true` for
+    pub(crate) fn without(self, skip: impl Fn(&StructField) -> bool) -> Self {
+        let keep = |fields: Vec<StructField<'a>>| {
+            fields
+                .into_iter()
+                .filter(|field| !skip(field))
+                .collect_vec()
+        };
+        match self {
+            Self::Struct(fields) => Self::Struct(keep(fields)),
+            Self::Enum(variants) => Self::Enum(
+                variants
+                    .into_iter()
+                    .map(|(variant, fields)| (variant, keep(fields)))
+                    .collect(),
+            ),
+        }
+    }
+
+    /**
+    Builds an expression out of the fields, for enums out of those of the active variant. `body` is
+    given every field with its position in [`Self::all`] and an expression borrowing its value from
+    `self`.
+    */
+    pub(crate) fn with_fields(
+        &self,
+        body: impl Fn(&[(usize, &StructField, TokenStream)]) -> TokenStream,
+    ) -> TokenStream {
+        self.with_borrowed_fields(Some(quote!(&)), body)
+    }
+
+    /// Like [`Self::with_fields`], borrowing the values mutably from `&mut self`
+    pub(crate) fn with_fields_mut(
+        &self,
+        body: impl Fn(&[(usize, &StructField, TokenStream)]) -> TokenStream,
+    ) -> TokenStream {
+        self.with_borrowed_fields(Some(quote!(&mut)), body)
+    }
+
+    /// Like [`Self::with_fields`], moving the values out of `self`
+    pub(crate) fn with_owned_fields(
+        &self,
+        body: impl Fn(&[(usize, &StructField, TokenStream)]) -> TokenStream,
+    ) -> TokenStream {
+        self.with_borrowed_fields(None, body)
+    }
+
+    /// `borrow` is `&` or `&mut`, `None` to move the values out of `self`
+    fn with_borrowed_fields(
+        &self,
+        borrow: Option<TokenStream>,
+        body: impl Fn(&[(usize, &StructField, TokenStream)]) -> TokenStream,
+    ) -> TokenStream {
+        let bindings = |fields: &[StructField]| {
+            fields
+                .iter()
+                .map(|field| match &field.member {
+                    Member::Named(ident) => quote!(#ident),
+                    member => {
+                        let ident = &field.ident;
+                        quote!(#member: #ident)
+                    }
+                })
+                .collect_vec()
+        };
+        match (self, borrow) {
+            (Self::Struct(fields), Some(borrow)) => {
+                let fields = fields
+                    .iter()
+                    .enumerate()
+                    .map(|(i, field)| {
+                        let member = &field.member;
+                        (i, field, quote!(#borrow self.#member))
+                    })
+                    .collect_vec();
+                body(&fields)
+            }
+            (Self::Struct(fields), None) => {
+                let bindings = bindings(fields);
+                let fields = fields
+                    .iter()
+                    .enumerate()
+                    .map(|(i, field)| (i, field, field.ident.to_token_stream()))
+                    .collect_vec();
+                let body = body(&fields);
+                quote! {
+                    let Self { #(#bindings,)* .. } = self;
+                    #body
+                }
+            }
+            (Self::Enum(variants), borrow) => {
+                let mut position = 0;
+                let arms = variants.iter().map(|(variant, fields)| {
+                    let bindings = bindings(fields);
+                    let fields = fields
+                        .iter()
+                        .enumerate()
+                        .map(|(i, field)| (position + i, field, field.ident.to_token_stream()))
+                        .collect_vec();
+                    position += fields.len();
+                    let body = body(&fields);
+                    quote!(Self::#variant { #(#bindings,)* .. } => #body)
+                });
+                match borrow {
+                    Some(_) => self.match_variant(arms),
+                    None => quote!(match self { #(#arms,)* }),
+                }
+            }
+        }
+    }
+
+    /**
+    Builds a `Vec` with an `element` for every field, for enums only for those of the active
+    variant. `element` is given the position of the field in [`Self::all`], the field and an
+    expression borrowing its value from `self`.
+    */
+    pub(crate) fn collect_values(
+        &self,
+        element: impl Fn(usize, &StructField, TokenStream) -> TokenStream,
+    ) -> TokenStream {
+        self.with_fields(|fields| {
+            let elements = fields
+                .iter()
+                .map(|(i, field, value)| element(*i, field, value.clone()));
+            quote!(::std::vec![#(#elements),*])
+        })
+    }
+
+    /// Like [`Self::collect_values`], for `element`s that don't need the values
+    pub(crate) fn collect_fields(
+        &self,
+        element: impl Fn(&StructField) -> TokenStream,
+    ) -> TokenStream {
+        match self {
+            Self::Struct(fields) => {
+                let elements = fields.iter().map(element);
+                quote!(::std::vec![#(#elements),*])
+            }
+            Self::Enum(variants) => self.match_variant(variants.iter().map(|(variant, fields)| {
+                let elements = fields.iter().map(&element);
+                quote!(Self::#variant { .. } => ::std::vec![#(#elements),*])
+            })),
+        }
+    }
+
+    fn match_variant(&self, arms: impl Iterator<Item = TokenStream>) -> TokenStream {
+        let arms = arms.collect_vec();
+        match arms.is_empty() {
+            // there is no value of an enum without variants to borrow from
+            true => quote!(match *self {}),
+            false => quote!(match self { #(#arms,)* }),
+        }
+    }
+}
+
+/**
+Lists the fields of the deriving struct or the variants of the deriving enum, erroring at the
+`union` keyword otherwise
+*/
+pub(crate) fn data_fields<'a>(
+    data: &'a Data,
+    attrs: &ContainerAttrs,
+    derive: &str,
+) -> syn::Result<DataFields<'a>> {
+    match data {
+        Data::Struct(DataStruct { fields, .. }) => {
+            Ok(DataFields::Struct(fields_of(fields, attrs, None)?))
+        }
+        Data::Enum(DataEnum { variants, .. }) => variants
+            .iter()
+            .map(|variant| {
+                let fields = fields_of(&variant.fields, attrs, Some(&variant.ident))?;
+                Ok((&variant.ident, fields))
+            })
+            .collect::<syn::Result<_>>()
+            .map(DataFields::Enum),
+        Data::Union(DataUnion { union_token, .. }) => Err(syn::Error::new(
+            union_token.span,
+            format!("`{derive}` can only be derived for structs and enums"),
+        )),
+    }
+}
+
+/// Lists the fields of the deriving struct, erroring at the `enum` or `union` keyword otherwise
+pub(crate) fn struct_fields<'a>(
+    data: &'a Data,
+    attrs: &ContainerAttrs,
+    derive: &str,
+) -> syn::Result<Vec<StructField<'a>>> {
+    let message = format!("`{derive}` can only be derived for structs");
+    match data {
+        Data::Struct(DataStruct { fields, .. }) => fields_of(fields, attrs, None),
+        Data::Enum(DataEnum { enum_token, .. }) => Err(syn::Error::new(enum_token.span, message)),
+        Data::Union(DataUnion { union_token, .. }) => {
+            Err(syn::Error::new(union_token.span, message))
+        }
+    }
+}
+
+/**
+Renders a type or visibility the way it is usually written, e.g. `Vec<&'a str>` instead of the
+`Vec < & 'a str >` of its tokens
+*/
+pub(crate) fn pretty_tokens(tokens: &impl ToTokens) -> String {
+    fn render(tokens: TokenStream, out: &mut String) {
+        // words written one after another, like `dyn Trait` or `'a mut`, need a space between them
+        let mut after_word = false;
+        for token in tokens {
+            match token {
+                TokenTree::Group(group) => {
+                    let (open, close) = match group.delimiter() {
+                        Delimiter::Parenthesis => ("(", ")"),
+                        Delimiter::Bracket => ("[", "]"),
+                        Delimiter::Brace => ("{", "}"),
+                        Delimiter::None => ("", ""),
+                    };
+                    out.push_str(open);
+                    render(group.stream(), out);
+                    out.push_str(close);
+                    after_word = group.delimiter() == Delimiter::None;
+                }
+                TokenTree::Ident(_) | TokenTree::Literal(_) => {
+                    if after_word {
+                        out.push(' ');
+                    }
+                    out.push_str(&token.to_string());
+                    after_word = true;
+                }
+                TokenTree::Punct(punct) => {
+                    match punct.as_char() {
+                        ',' | ';' => out.push_str(&format!("{} ", punct.as_char())),
+                        '+' | '=' => out.push_str(&format!(" {} ", punct.as_char())),
+                        '-' if punct.spacing() == Spacing::Joint => out.push_str(" -"),
+                        '>' if out.ends_with('-') => out.push_str("> "),
+                        chr => out.push(chr),
+                    }
+                    after_word = false;
+                }
+            }
+        }
+    }
+    let mut name = String::new();
+    render(tokens.to_token_stream(), &mut name);
+    name
+}
+
+/// Parses a generated name as an identifier, `None` if it isn't a valid one
+pub(crate) fn parse_ident(name: &str) -> Option<Ident> {
+    syn::parse_str(name).ok()
+}
+
+/**
+Errors at the second field reported or looked up by a name another field already uses, as
+`rename` and `alias` could make the names ambiguous
+*/
+pub(crate) fn check_unique_names(fields: &[StructField]) -> syn::Result<()> {
+    let mut seen = HashSet::new();
+    for field in fields {
+        let aliases = field
+            .attrs
+            .aliases
+            .iter()
+            .map(|alias| (alias.value(), alias.span()));
+        for (name, span) in
+            iter::once((field.external_name.clone(), field.ident.span())).chain(aliases)
+        {
+            if !seen.insert(name.clone()) {
+                return Err(syn::Error::new(
+                    span,
+                    format!("the field name `{name}` is used more than once"),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Lists the fields of a struct or of the enum variant `variant`, unit structs and variants having none
+fn fields_of<'a>(
+    fields: &'a Fields,
+    container: &ContainerAttrs,
+    variant: Option<&'a Ident>,
+) -> syn::Result<Vec<StructField<'a>>> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let attrs = FieldAttrs::parse(&field.attrs)?;
+            let (member, ident, name) = match &field.ident {
+                Some(ident) => (
+                    Member::Named(ident.clone()),
+                    ident.clone(),
+                    ident.unraw().to_string(),
+                ),
+                None => (
+                    Member::Unnamed(Index::from(i)),
+                    format_ident!("_{}", i),
+                    i.to_string(),
+                ),
+            };
+            let external_name = match (&attrs.rename, container.rename_all) {
+                (Some(rename), _) => rename.value(),
+                (None, Some(rule)) => rule.apply(&name),
+                (None, None) => name.clone(),
+            };
+            Ok(StructField {
+                field,
+                variant,
+                index: i,
+                member,
+                ident,
+                name,
+                external_name,
+                attrs,
+            })
+        })
+        .collect()
+}
+
+/**
+Builds a `Vec` out of the values `with_fields` hands to its body, converting each with `element`,
+or extending it by what `nested` returns for a flattened field given its type
+*/
+pub(crate) fn flattened_values(
+    fields: &[(usize, &StructField, TokenStream)],
+    element: impl Fn(&TokenStream) -> TokenStream,
+    nested: impl Fn(&Type, &TokenStream) -> TokenStream,
+) -> TokenStream {
+    if !fields.iter().any(|(_, field, _)| field.attrs.flatten) {
+        let elements = fields.iter().map(|(_, _, value)| element(value));
+        return quote!(::std::vec![#(#elements),*]);
+    }
+    let values = fields
+        .iter()
+        .map(|(_, field, value)| match field.attrs.flatten {
+            true => {
+                let nested = nested(field.ty(), value);
+                quote!(values.extend(#nested);)
+            }
+            false => {
+                let element = element(value);
+                quote!(values.push(#element);)
+            }
+        });
+    quote! {
+        let mut values = ::std::vec::Vec::new();
+        #(#values)*
+        values
+    }
+}
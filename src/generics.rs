@@ -0,0 +1,342 @@
+use std::collections::HashSet;
+
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, ToTokens};
+use syn::{
+    punctuated::Punctuated,
+    visit::{self, Visit},
+    GenericParam, Generics, Ident, Lifetime, LifetimeParam, Type, WherePredicate,
+};
+
+/// Collects every generic parameter of the struct that is named inside the visited syntax
+struct UsedParams<'g> {
+    generics: &'g Generics,
+    used: HashSet<String>,
+}
+
+impl<'g> UsedParams<'g> {
+    fn new(generics: &'g Generics) -> Self {
+        Self {
+            generics,
+            used: HashSet::new(),
+        }
+    }
+
+    fn is_param(&self, name: &str) -> bool {
+        self.generics
+            .params
+            .iter()
+            .any(|param| param_name(param) == name)
+    }
+}
+
+impl<'ast, 'g> Visit<'ast> for UsedParams<'g> {
+    fn visit_path(&mut self, path: &'ast syn::Path) {
+        if path.leading_colon.is_none() {
+            if let Some(first) = path.segments.first() {
+                let name = first.ident.to_string();
+                if self.is_param(&name) {
+                    self.used.insert(name);
+                }
+            }
+        }
+        visit::visit_path(self, path);
+    }
+
+    fn visit_lifetime(&mut self, lifetime: &'ast Lifetime) {
+        let name = lifetime.to_string();
+        if self.is_param(&name) {
+            self.used.insert(name);
+        }
+    }
+}
+
+fn param_name(param: &GenericParam) -> String {
+    match param {
+        GenericParam::Lifetime(param) => param.lifetime.to_string(),
+        GenericParam::Type(param) => param.ident.to_string(),
+        GenericParam::Const(param) => param.ident.to_string(),
+    }
+}
+
+/// Returns the names of all generic parameters referenced by the syntax `visit` walks
+fn params_in(generics: &Generics, visit: impl FnOnce(&mut UsedParams)) -> HashSet<String> {
+    let mut visitor = UsedParams::new(generics);
+    visit(&mut visitor);
+    visitor.used
+}
+
+/**
+Returns the subset of `generics` that is needed to name all of `types`.
+
+Parameters that only show up in the bounds of a used parameter or in a where-predicate about
+a used parameter are pulled in as well, so the result is always a valid set of generics.
+Defaults of type and const parameters are dropped.
+*/
+pub(crate) fn filter_generics<'a>(
+    generics: &Generics,
+    types: impl IntoIterator<Item = &'a Type>,
+) -> Generics {
+    let mut used = HashSet::new();
+    for ty in types {
+        used.extend(params_in(generics, |v| v.visit_type(ty)));
+    }
+
+    // pull in everything the bounds of the used parameters talk about
+    loop {
+        let before = used.len();
+        for param in &generics.params {
+            if used.contains(&param_name(param)) {
+                used.extend(params_in(generics, |v| v.visit_generic_param(param)));
+            }
+        }
+        if let Some(where_clause) = &generics.where_clause {
+            for predicate in &where_clause.predicates {
+                let bounded = match predicate {
+                    WherePredicate::Type(predicate) => {
+                        params_in(generics, |v| v.visit_type(&predicate.bounded_ty))
+                    }
+                    WherePredicate::Lifetime(predicate) => {
+                        params_in(generics, |v| v.visit_lifetime(&predicate.lifetime))
+                    }
+                    _ => HashSet::new(),
+                };
+                if !bounded.is_empty() && bounded.is_subset(&used) {
+                    used.extend(params_in(generics, |v| v.visit_where_predicate(predicate)));
+                }
+            }
+        }
+        if used.len() == before {
+            break;
+        }
+    }
+
+    let params = params_without_defaults(generics)
+        .into_iter()
+        .filter(|param| used.contains(&param_name(param)))
+        .collect::<Punctuated<_, _>>();
+
+    let where_clause = generics.where_clause.clone().map(|mut where_clause| {
+        where_clause.predicates = where_clause
+            .predicates
+            .into_iter()
+            .filter(|predicate| {
+                params_in(generics, |v| v.visit_where_predicate(predicate)).is_subset(&used)
+            })
+            .collect();
+        where_clause
+    });
+
+    Generics {
+        lt_token: Some(Default::default()),
+        params,
+        gt_token: Some(Default::default()),
+        where_clause,
+    }
+}
+
+/// Returns the parameters of `generics` with the defaults of type and const parameters removed
+pub(crate) fn params_without_defaults(generics: &Generics) -> Vec<GenericParam> {
+    generics
+        .params
+        .iter()
+        .cloned()
+        .map(|param| match param {
+            GenericParam::Type(mut param) => {
+                param.eq_token = None;
+                param.default = None;
+                GenericParam::Type(param)
+            }
+            GenericParam::Const(mut param) => {
+                param.eq_token = None;
+                param.default = None;
+                GenericParam::Const(param)
+            }
+            param => param,
+        })
+        .collect()
+}
+
+/// Returns the parameters of `generics` the way they are passed as arguments, e.g. `'a, T, N`
+pub(crate) fn param_args(generics: &Generics) -> Vec<TokenStream> {
+    generics
+        .params
+        .iter()
+        .map(|param| match param {
+            GenericParam::Lifetime(param) => param.lifetime.to_token_stream(),
+            GenericParam::Type(param) => param.ident.to_token_stream(),
+            GenericParam::Const(param) => param.ident.to_token_stream(),
+        })
+        .collect()
+}
+
+/**
+Picks a lifetime named like `name` (e.g. `'a`) that does not collide with any lifetime of
+`generics`, and returns it together with a copy of `generics` that has it prepended.
+*/
+pub(crate) fn with_lifetime(generics: &Generics, name: &str) -> (Lifetime, Generics) {
+    let taken = generics
+        .lifetimes()
+        .map(|param| param.lifetime.ident.to_string())
+        .collect::<HashSet<_>>();
+    let mut name = name.to_owned();
+    while taken.contains(&name) {
+        name.insert(0, '_');
+    }
+    let lifetime = Lifetime::new(&format!("'{name}"), Span::call_site());
+
+    let mut generics = generics.clone();
+    generics.params.insert(
+        0,
+        GenericParam::Lifetime(LifetimeParam::new(lifetime.clone())),
+    );
+    (lifetime, generics)
+}
+
+/// Returns the ident of the generic type parameter `ty` consists of, if it is just that
+pub(crate) fn as_type_param<'a>(generics: &Generics, ty: &'a Type) -> Option<&'a Ident> {
+    match ty {
+        Type::Path(syn::TypePath { qself: None, path }) => path
+            .get_ident()
+            .filter(|ident| generics.type_params().any(|param| param.ident == **ident)),
+        Type::Paren(syn::TypeParen { elem, .. }) | Type::Group(syn::TypeGroup { elem, .. }) => {
+            as_type_param(generics, elem)
+        }
+        _ => None,
+    }
+}
+
+/// Returns if `a` and `b` could name the same type for some arguments of `generics`, comparing them
+/// by their structure with the parameters standing in for any type, lifetime or length
+pub(crate) fn could_unify(generics: &Generics, a: &Type, b: &Type) -> bool {
+    // an associated type or a macro could be anything as well
+    let is_any = |ty: &Type| match ty {
+        Type::Path(syn::TypePath { qself: Some(_), .. }) | Type::Macro(_) | Type::Infer(_) => true,
+        Type::Path(syn::TypePath { qself: None, path }) => {
+            path.leading_colon.is_none()
+                && path.segments.first().is_some_and(|first| {
+                    generics
+                        .type_params()
+                        .any(|param| param.ident == first.ident)
+                })
+        }
+        _ => false,
+    };
+    let unify = |a: &Type, b: &Type| could_unify(generics, a, b);
+    match (a, b) {
+        (
+            Type::Paren(syn::TypeParen { elem, .. }) | Type::Group(syn::TypeGroup { elem, .. }),
+            _,
+        ) => unify(elem, b),
+        (
+            _,
+            Type::Paren(syn::TypeParen { elem, .. }) | Type::Group(syn::TypeGroup { elem, .. }),
+        ) => unify(a, elem),
+        _ if is_any(a) || is_any(b) => true,
+        (Type::Path(a), Type::Path(b)) => {
+            a.path.segments.len() == b.path.segments.len()
+                && a.path.segments.iter().zip(&b.path.segments).all(|(a, b)| {
+                    a.ident == b.ident
+                        && match (&a.arguments, &b.arguments) {
+                            (
+                                syn::PathArguments::AngleBracketed(a),
+                                syn::PathArguments::AngleBracketed(b),
+                            ) => {
+                                a.args.len() == b.args.len()
+                                    && a.args.iter().zip(&b.args).all(|(a, b)| match (a, b) {
+                                        (
+                                            syn::GenericArgument::Type(a),
+                                            syn::GenericArgument::Type(b),
+                                        ) => unify(a, b),
+                                        _ => true,
+                                    })
+                            }
+                            (syn::PathArguments::None, syn::PathArguments::None) => true,
+                            // the arguments of `Fn(...) -> ...` aren't looked into
+                            (
+                                syn::PathArguments::Parenthesized(_),
+                                syn::PathArguments::Parenthesized(_),
+                            ) => true,
+                            _ => false,
+                        }
+                })
+        }
+        (Type::Reference(a), Type::Reference(b)) => {
+            a.mutability.is_some() == b.mutability.is_some() && unify(&a.elem, &b.elem)
+        }
+        (Type::Ptr(a), Type::Ptr(b)) => {
+            a.mutability.is_some() == b.mutability.is_some() && unify(&a.elem, &b.elem)
+        }
+        (Type::Slice(a), Type::Slice(b)) => unify(&a.elem, &b.elem),
+        (Type::Array(a), Type::Array(b)) => unify(&a.elem, &b.elem),
+        (Type::Tuple(a), Type::Tuple(b)) => {
+            a.elems.len() == b.elems.len() && a.elems.iter().zip(&b.elems).all(|(a, b)| unify(a, b))
+        }
+        _ => a == b,
+    }
+}
+
+/// Returns if the enum with a variant for each of `types` gets a `From` impl for `ty`, and a
+/// `TryFrom` impl converting it back, as these impls mustn't overlap
+pub(crate) fn converts(generics: &Generics, types: &[&Type], ty: &Type) -> (bool, bool) {
+    // a bare type parameter can't be converted back out of the enum (that impl would overlap
+    // with the blanket impls in std), and as it could be any of the other types, it only gets a
+    // `From` impl when it is the only one
+    if as_type_param(generics, ty).is_some() {
+        return (types.len() == 1, false);
+    }
+    // neither can a generic type that could turn out to be another one, like `Vec<T>` next to
+    // `Vec<u8>`, whose impls would then overlap
+    let is_generic = !filter_generics(generics, [ty]).params.is_empty();
+    let overlaps = is_generic
+        && types.iter().any(|other| {
+            *other != ty
+                && as_type_param(generics, other).is_none()
+                && could_unify(generics, ty, other)
+        });
+    (!overlaps, !overlaps)
+}
+
+/// Returns a copy of `generics` with `predicates` added to its where-clause
+pub(crate) fn with_predicates(
+    generics: &Generics,
+    predicates: impl IntoIterator<Item = WherePredicate>,
+) -> Generics {
+    let mut generics = generics.clone();
+    generics.make_where_clause().predicates.extend(predicates);
+    generics
+}
+
+/// The generics of an enum generated to hold references to the fields of the deriving type
+pub(crate) struct BorrowingGenerics {
+    /// The parameters of the deriving type needed to name the fields, passed as arguments
+    pub(crate) args: Vec<TokenStream>,
+    /// The lifetime of the references
+    pub(crate) lifetime: Lifetime,
+    /// The generics of the enum
+    pub(crate) generics: Generics,
+    /// The arguments naming the enum with an elided lifetime, `None` when it has no lifetime
+    pub(crate) return_args: Option<TokenStream>,
+}
+
+/// Returns the generics of an enum holding references to values of each of `types`
+pub(crate) fn borrowing_generics(generics: &Generics, types: &[&Type]) -> BorrowingGenerics {
+    let used_generics = filter_generics(generics, types.iter().copied());
+    let args = param_args(&used_generics);
+    let (lifetime, enum_generics) = with_lifetime(&used_generics, "a");
+    // without any variants there is nothing to borrow, so the lifetime would go unused
+    match types.is_empty() {
+        true => BorrowingGenerics {
+            args,
+            lifetime,
+            generics: used_generics,
+            return_args: None,
+        },
+        false => BorrowingGenerics {
+            return_args: Some(quote!(<'_, #(#args),*>)),
+            args,
+            lifetime,
+            generics: enum_generics,
+        },
+    }
+}
@@ -1,11 +1,26 @@
 #![allow(clippy::redundant_clone)]
+use std::iter;
+
 use itertools::Itertools;
-use proc_macro::{Span, TokenStream};
+use proc_macro::TokenStream;
+
+mod attrs;
+mod case;
+mod fields;
+mod generics;
+mod variants;
+
+use attrs::{docs, ContainerAttrs};
+use fields::{
+    check_unique_names, data_fields, flattened_values, parse_ident, pretty_tokens, struct_fields,
+    DataFields, StructField,
+};
 use quote::{format_ident, quote, ToTokens};
 use syn::{
-    self, parse_macro_input, Attribute, Data::Struct, DataStruct, DeriveInput, Fields::Named,
-    FieldsNamed, Ident, Type,
+    self, ext::IdentExt, parse_macro_input, parse_quote, DeriveInput, Generics, Ident, Path, Type,
+    WherePredicate,
 };
+use variants::type_variants;
 
 /**
 Lets you iterate over structs
@@ -43,7 +58,7 @@ This now let's you use it like this:
 use struct_tools_derive::StructIterTools;
 
 #[derive(StructIterTools)]
-#[StructFields]
+#[struct_tools(fields)]
 pub struct Foo{
     field1: i32,
     field2: String,
@@ -54,6 +69,33 @@ let fields = Foo::fields();
 assert_eq!(fields,vec![String::from("field1"), String::from("field2")])
 ```
 
+The names are also available as constants, without allocating: `FIELD_NAMES` holds all of them,
+`FIELD_COUNT` their number and `FIELD_FIELD1`, ... the name of each field. A field named `count` or
+`names` has to name its constant with `#[struct_tools(field_const = "...")]`. `field_index` is a
+`const fn`.
+
+```rust
+use struct_tools_derive::StructIterTools;
+
+#[derive(StructIterTools)]
+#[struct_tools(fields)]
+pub struct Foo{
+    field1: i32,
+    field2: String,
+}
+
+const FIELD2: Option<usize> = Foo::field_index("field2");
+
+assert_eq!(Foo::FIELD_NAMES, ["field1", "field2"]);
+assert_eq!(Foo::FIELD_FIELD1, "field1");
+assert_eq!(FIELD2, Some(1));
+let widths = [0; Foo::FIELD_COUNT];
+```
+
+With the `struct-tools` crate named as `#[struct_tools(crate = "struct_tools")]`, `STRUCT_INFO` and
+`field_infos()` additionally describe the struct and each of its fields, with their types, doc
+comments and visibilities.
+
 #### values
 
 ```rust
@@ -61,7 +103,7 @@ use struct_tools_derive::StructIterTools;
 
 // Default just for demonstration
 #[derive(StructIterTools, Default)]
-#[StructValues]
+#[struct_tools(values)]
 pub struct Foo{
     field1: i32,
     field2: String,
@@ -100,8 +142,7 @@ use struct_tools_derive::StructIterTools;
 
 // Default just for demonstration
 #[derive(StructIterTools, Default)]
-#[StructFields]
-#[StructValues]
+#[struct_tools(fields, values)]
 pub struct Foo{
     field1: i32,
     field2: String,
@@ -131,43 +172,322 @@ let instance = Foo::default();
 let f_v = instance.fields_and_values::<FooEnum>();
 
 assert_eq!(f_v,vec![(String::from("field1"), FooEnum::I32(0)), (String::from("field2"), FooEnum::String(String::new()))])
+```
+
+With the `struct-tools` crate named as `crate = "..."`, `values` also generates `visit_fields` and
+`visit_fields_mut`, which hand the name and a reference to every field to a `FieldVisitor` or
+`FieldVisitorMut`, keeping the type of each field instead of converting it into an enum.
+
+A struct can be built back from its values with `from_values`, and with `fields` also from
+`from_fields_and_values` or a map returned by `to_map`. The values are converted with `TryInto`,
+and a `{structname}FromValuesError` lists every field that was missing, duplicated, unknown or of
+the wrong variant:
+
+```rust
+use struct_tools_derive::{StructEnum, StructIterTools};
+
+#[derive(Debug, PartialEq, StructIterTools, StructEnum)]
+#[struct_tools(fields, values)]
+pub struct Foo{
+    field1: i32,
+    field2: String,
+}
+
+let values = vec![FooEnum::I32(1), FooEnum::String(String::from("two"))];
+assert_eq!(Foo::from_values(values), Ok(Foo{ field1: 1, field2: String::from("two") }));
+
+let error = Foo::from_fields_and_values([("field3", FooEnum::I32(3))]).unwrap_err();
+assert_eq!(error.missing, vec!["field1", "field2"]);
+assert_eq!(error.unknown, vec![String::from("field3")]);
+```
+
+With the `struct-tools` crate named as `crate = "..."`, a field marked `#[struct_tools(flatten)]` is
+reported as the fields of its own struct by `fields`, `values`, `fields_and_values` and `to_map`,
+named `"{field}.{nested field}"`, or with the separator given as `flatten_separator = "..."`.
+`values_ref`, `into_values`, `visit_fields` and the `from_values` methods treat it the same way.
+`FIELD_NAMES`, `FIELD_COUNT` and `field_index` keep the field as a whole, while
+`FLATTENED_FIELD_COUNT` and `flattened_field_index` count and look up the names `fields` lists.
+The other derives treat the field as a whole as well.
+
+#### enums
+
+Derived for an enum, `fields` generates `variants_fields`, listing every variant with the names of
+its fields, and `variant_fields`, listing those of the active variant. `values` returns the values
+of the active variant.
+
+```rust
+use struct_tools_derive::StructIterTools;
+
+#[derive(StructIterTools)]
+#[struct_tools(fields)]
+pub enum Shape {
+    Circle { radius: f64 },
+    Rect(f64, f64),
+}
+
+assert_eq!(Shape::variants_fields()[0], (String::from("Circle"), vec![String::from("radius")]));
+assert_eq!(Shape::Rect(1.0, 2.0).variant_fields(), vec![String::from("0"), String::from("1")]);
+```
 */
-#[proc_macro_derive(StructIterTools, attributes(StructFields, StructValues))]
+#[proc_macro_derive(StructIterTools, attributes(struct_tools, StructFields, StructValues))]
 pub fn derive_struct_iter_tools(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_struct_iter_tools(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_struct_iter_tools(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
     let DeriveInput {
-        attrs, ident, data, ..
-    } = parse_macro_input!(input as DeriveInput);
+        attrs,
+        vis,
+        ident,
+        data,
+        generics,
+    } = input;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let struct_docs = docs(&attrs);
+    let attrs = ContainerAttrs::parse(&attrs)?;
+    let runtime = runtime_crate(&attrs);
+    let vis = attrs.vis.as_ref().unwrap_or(&vis);
+
+    let derive_fields: bool = attrs.fields;
+    let derive_values: bool = attrs.values;
+
+    let item_fields = data_fields(&data, &attrs, "StructIterTools")?;
+    let deprecations = deprecations(&attrs, item_fields.all(), &["StructFields", "StructValues"]);
+    // a struct built from its values fills the skipped fields the way the builders do
+    let skipped = match &item_fields {
+        DataFields::Struct(fields) => {
+            let skipped = fields.iter().filter(|field| field.attrs.skip.iter);
+            let default_bounds = skipped
+                .clone()
+                .filter(|field| field.attrs.builder_default.is_none())
+                .map(|field| default_bound(field.ty()))
+                .collect_vec();
+            Some((skipped_fields(skipped), default_bounds))
+        }
+        DataFields::Enum(_) => None,
+    };
+    let item_fields = item_fields.without(|field| field.attrs.skip.iter);
+    let fields = item_fields.all();
+
+    // a flattened field lists the fields of its own struct, through the traits that struct implements
+    let flattened = match fields.iter().find(|field| field.attrs.flatten) {
+        Some(field) if field.variant.is_some() => {
+            return Err(syn::Error::new_spanned(
+                field.field,
+                "only the fields of structs can be flattened",
+            ))
+        }
+        Some(field) if runtime.is_none() => {
+            return Err(syn::Error::new_spanned(
+                field.field,
+                "flattening a field needs the traits of `struct-tools`, named with `#[struct_tools(crate = \"struct_tools\")]`",
+            ))
+        }
+        Some(_) => true,
+        None => false,
+    };
+    let separator = attrs
+        .flatten_separator
+        .as_ref()
+        .map_or_else(|| String::from("."), syn::LitStr::value);
 
-    let attrs: Vec<String> = attrs
+    let field_types = fields.iter().map(|field| field.ty()).unique().collect_vec();
+    let (flattened_types, value_types): (Vec<_>, Vec<_>) = fields
         .iter()
-        .filter_map(|attr| attr.path().get_ident())
-        .map(|attr| attr.to_string())
-        .collect();
-
-    let derive_fields: bool = attrs.contains(&"StructFields".to_string());
-    let derive_values: bool = attrs.contains(&"StructValues".to_string());
-
-    let fields = match data {
-        Struct(DataStruct {
-            fields: Named(FieldsNamed { ref named, .. }),
-            ..
-        }) => named,
-        _ => todo!(),
+        .map(|field| (field.attrs.flatten, field.ty()))
+        .unique_by(|(_, ty)| *ty)
+        .partition(|(flatten, _)| *flatten);
+    let value_types = value_types.into_iter().map(|(_, ty)| ty).collect_vec();
+    let flattened_types = flattened_types.into_iter().map(|(_, ty)| ty).collect_vec();
+    let clone_bounds = value_types.iter().map(|ty| clone_bound(ty));
+    let value_bounds = quote! {
+        E: #(From<#value_types>)+*,
+        #(#clone_bounds,)*
+        #(#flattened_types: #runtime::FieldValues<E>,)*
     };
+    let (lifetime, _) = generics::with_lifetime(&generics, "a");
+    // the number of names `fields` lists for each field
+    let field_counts = fields
+        .iter()
+        .map(|field| match field.attrs.flatten {
+            true => {
+                let ty = field.ty();
+                quote!(<#ty as #runtime::FieldNames>::FLATTENED_FIELD_COUNT)
+            }
+            false => quote!(1),
+        })
+        .collect_vec();
+    let field_count = fields.len();
+    // the number of values `values` returns, which is the number of names `fields` lists
+    let value_count = match flattened {
+        true => quote!(#(#field_counts)+*),
+        false => field_count.to_token_stream(),
+    };
+
+    let values = item_fields.with_fields(|fields| {
+        flattened_values(
+            fields,
+            |value| quote!(E::from(::std::clone::Clone::clone(#value))),
+            |ty, value| quote!(<#ty as #runtime::FieldValues<E>>::values(#value)),
+        )
+    });
+    let values_ref = item_fields.with_fields(|fields| {
+        flattened_values(
+            fields,
+            |value| quote!(E::from(#value)),
+            |ty, value| quote!(<#ty as #runtime::FieldValuesRef<#lifetime, E>>::values_ref(#value)),
+        )
+    });
+    let ref_bounds = quote! {
+        E: #(From<&#lifetime #value_types>)+*,
+        #(#flattened_types: #runtime::FieldValuesRef<#lifetime, E>,)*
+    };
+    let into_bounds = quote! {
+        E: #(From<#value_types>)+*,
+        #(#flattened_types: #runtime::IntoFieldValues<E>,)*
+    };
+    // taking the struct apart doesn't work if it implements `Drop`, so it has to be asked for
+    let into_values = attrs.into.then(|| {
+        let values = item_fields.with_owned_fields(|fields| {
+            flattened_values(
+                fields,
+                |value| quote!(E::from(#value)),
+                |ty, value| quote!(<#ty as #runtime::IntoFieldValues<E>>::into_values(#value)),
+            )
+        });
+        quote! {
+            /// returns the values of this Instance, moving them out instead of cloning them
+            pub fn into_values<E>(self) -> ::std::vec::Vec<E>
+            where
+            #into_bounds
+            {
+                #values
+            }
+        }
+    });
 
-    let field_ids = fields.iter().filter_map(|field| field.ident.clone());
+    let fields_quote = match (derive_fields, &item_fields) {
+        (true, DataFields::Struct(fields)) => {
+            check_unique_names(fields)?;
+            let fields_vec = fields
+                .iter()
+                .map(|field| field.external_name.as_str())
+                .collect_vec();
+            let field_consts = fields
+                .iter()
+                .map(|field| {
+                    let name = match &field.attrs.field_const {
+                        Some(name) => name.clone(),
+                        None => format_ident!(
+                            "FIELD_{}",
+                            field.name.to_uppercase(),
+                            span = field.ident.span()
+                        ),
+                    };
+                    // `count` and `names` would clash with the constants of all fields
+                    if name == "FIELD_COUNT" || name == "FIELD_NAMES" {
+                        return Err(syn::Error::new(
+                            name.span(),
+                            format!(
+                                "the constant `{name}` of this field clashes with the one of all fields, name it with `#[struct_tools(field_const = \"...\")]`"
+                            ),
+                        ));
+                    }
+                    let external_name = &field.external_name;
+                    let doc = format!("the name of the field `{}`", field.name);
+                    Ok(quote! {
+                        #[doc = #doc]
+                        pub const #name: &'static str = #external_name;
+                    })
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            let field_patterns = fields.iter().map(|field| field.name_pattern());
+            let field_indices = 0..fields.len();
+            // `FIELD_NAMES`, `FIELD_COUNT` and `field_index` keep a flattened field as a whole, so
+            // they stay const, and the names `fields` lists are counted and looked up separately
+            let flattened_items = flattened.then(|| {
+                let lookups = fields.iter().enumerate().map(|(i, field)| {
+                    let offset = match &field_counts[..i] {
+                        [] => quote!(0),
+                        before => quote!(#(#before)+*),
+                    };
+                    match field.attrs.flatten {
+                        true => {
+                            let ty = field.ty();
+                            let prefixes = field.flattened_prefixes(&separator);
+                            quote! {
+                                for prefix in [#(#prefixes),*] {
+                                    if let ::std::option::Option::Some(name) = name.strip_prefix(prefix) {
+                                        if let ::std::option::Option::Some(index) = <#ty as #runtime::FieldNames>::flattened_field_index(name) {
+                                            return ::std::option::Option::Some(#offset + index);
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        false => {
+                            let pattern = field.name_pattern();
+                            quote! {
+                                if let #pattern = name.as_bytes() {
+                                    return ::std::option::Option::Some(#offset);
+                                }
+                            }
+                        }
+                    }
+                });
+                quote! {
+                    /// the number of names [`Self::fields`] returns, counting the fields of a flattened field
+                    pub const FLATTENED_FIELD_COUNT: usize = #value_count;
+
+                    /// returns the position of the field named `name` in [`Self::fields`], also accepting its aliases
+                    /// and the paths of the fields of a flattened field
+                    pub fn flattened_field_index(name: &str) -> ::std::option::Option<usize> {
+                        #(#lookups)*
+                        ::std::option::Option::None
+                    }
+                }
+            });
+            let all_fields = match flattened {
+                true => {
+                    let fields = fields.iter().map(|field| {
+                        let name = &field.external_name;
+                        let ty = field.ty();
+                        let prefix = format!("{name}{separator}");
+                        match field.attrs.flatten {
+                            true => quote! {
+                                fields.extend(
+                                    <#ty as #runtime::FieldNames>::fields()
+                                        .into_iter()
+                                        .map(|field| ::std::format!("{}{}", #prefix, field))
+                                );
+                            },
+                            false => {
+                                quote!(fields.push(::std::string::ToString::to_string(#name));)
+                            }
+                        }
+                    });
+                    quote! {
+                        let mut fields = ::std::vec::Vec::new();
+                        #(#fields)*
+                        fields
+                    }
+                }
+                false => quote!(vec![#(#fields_vec.to_string()),*]),
+            };
+            Some(quote! {
+            impl #impl_generics #ident #ty_generics #where_clause {
+                /// the names of the Structs fields, as returned by [`Self::fields`] unless a field is flattened
+                pub const FIELD_NAMES: &'static [&'static str] = &[#(#fields_vec),*];
 
-    let field_types = fields.iter().map(|field| &field.ty).unique();
-    let types = quote!(#(From<#field_types>)+*);
+                /// the number of the Structs fields
+                pub const FIELD_COUNT: usize = #field_count;
 
-    let fields_vec: std::vec::Vec<std::string::String> = fields
-        .iter()
-        .filter_map(|field| field.ident.as_ref().map(|id| format!("{id}")))
-        .collect::<Vec<String>>();
+                #(#field_consts)*
 
-    let fields_quote = match derive_fields {
-        true => Some(quote! {
-            impl #ident {
                 /**
 
                 returns the names of the Structs fields
@@ -176,7 +496,7 @@ pub fn derive_struct_iter_tools(input: TokenStream) -> TokenStream {
                 use struct_tools_derive::StructIterTools;
 
                 #[derive(StructIterTools)]
-                #[StructFields]
+                #[struct_tools(fields)]
                 pub struct Foo{
                     field1: i32,
                     field2: String,
@@ -188,15 +508,75 @@ pub fn derive_struct_iter_tools(input: TokenStream) -> TokenStream {
                 ```
                  */
                 pub fn fields() -> ::std::vec::Vec<::std::string::String>{
-                    vec![#(#fields_vec.to_string()),*]
+                    #all_fields
                 }
+
+                /// returns the position of the field named `name` in [`Self::FIELD_NAMES`], also accepting its aliases
+                pub const fn field_index(name: &str) -> ::std::option::Option<usize> {
+                    match name.as_bytes() {
+                        #(#field_patterns => ::std::option::Option::Some(#field_indices),)*
+                        _ => ::std::option::Option::None,
+                    }
+                }
+
+                #flattened_items
             }
-        }),
-        false => None,
+            })
+        }
+        (true, DataFields::Enum(variants)) => {
+            for (_, fields) in variants {
+                check_unique_names(fields)?;
+            }
+            let variants_vec = variants.iter().map(|(variant, fields)| {
+                let variant_name = variant.unraw().to_string();
+                let field_names = fields.iter().map(|field| field.external_name.as_str());
+                quote!((#variant_name.to_string(), vec![#(#field_names.to_string()),*]))
+            });
+            let active_fields = item_fields.collect_fields(|field| {
+                let name = &field.external_name;
+                quote!(#name.to_string())
+            });
+            Some(quote! {
+                impl #impl_generics #ident #ty_generics #where_clause {
+                    /**
+
+                    returns the names of the Enums variants, each with the names of its fields
+
+                    ```rust
+                    use struct_tools_derive::StructIterTools;
+
+                    #[derive(StructIterTools)]
+                    #[struct_tools(fields)]
+                    pub enum Foo{
+                        Variant1 { field1: i32 },
+                        Variant2(String),
+                        //{...}
+                    }
+
+                    let fields = Foo::variants_fields();
+                    assert_eq!(fields,vec![
+                        (String::from("Variant1"), vec![String::from("field1")]),
+                        (String::from("Variant2"), vec![String::from("0")]),
+                        //{...}
+                    ])
+                    ```
+                     */
+                    pub fn variants_fields() -> ::std::vec::Vec<(::std::string::String, ::std::vec::Vec<::std::string::String>)> {
+                        vec![#(#variants_vec),*]
+                    }
+
+                    /// returns the names of the fields of the active variant
+                    pub fn variant_fields(&self) -> ::std::vec::Vec<::std::string::String> {
+                        #active_fields
+                    }
+                }
+            })
+        }
+        (false, _) => None,
     };
     let values_quote = match derive_values {
         true => Some(quote! {
-            impl #ident{
+            impl #impl_generics #ident #ty_generics #where_clause {
                 /**
 
                 returns the values of this Instance
@@ -206,7 +586,7 @@ pub fn derive_struct_iter_tools(input: TokenStream) -> TokenStream {
 
                 // Default just for demonstration
                 #[derive(StructIterTools, Default)]
-                #[StructValues]
+                #[struct_tools(values)]
                 pub struct Foo{
                     field1: i32,
                     field2: String,
@@ -240,17 +620,379 @@ pub fn derive_struct_iter_tools(input: TokenStream) -> TokenStream {
                  */
                 pub fn values<E>(&self) -> ::std::vec::Vec<E>
                 where
-                E: #types
+                #value_bounds
+                {
+                    #values
+                }
+
+                /// returns the values of this Instance as `E`, converted from references instead of clones
+                pub fn values_ref<#lifetime, E>(&#lifetime self) -> ::std::vec::Vec<E>
+                where
+                #ref_bounds
                 {
-                    vec![#(E::from(self.#field_ids.clone())),*]
+                    #values_ref
                 }
+
+                #into_values
             }
         }),
         false => None,
     };
+    // an enum can't be built without knowing which variant the values are for
+    let from_values_quote = match (derive_values, &item_fields, skipped) {
+        (
+            true,
+            DataFields::Struct(fields),
+            Some(((skipped_members, skipped_values), default_bounds)),
+        ) => {
+            let error = generated_name(&attrs.from_values_error, &ident, "FromValuesError");
+            let struct_name = ident.unraw().to_string();
+            let members = fields.iter().map(|field| &field.member).collect_vec();
+            let locals = fields
+                .iter()
+                .map(|field| format_ident!("__{}", field.ident.unraw()))
+                .collect_vec();
+            let from_bounds = quote! {
+                E: #(::std::convert::TryInto<#value_types>)+*,
+                #(#flattened_types: #runtime::FromFieldValues<E> + #runtime::FieldNames,)*
+                #(#default_bounds),*
+            };
+            // a flattened field is built by its own `from_values`, and reported as a whole if that fails
+            let build_flattened = |ty: &Type, name: &str| {
+                quote! {
+                    match <#ty as #runtime::FromFieldValues<E>>::from_values(values) {
+                        ::std::result::Result::Ok(value) => ::std::option::Option::Some(value),
+                        ::std::result::Result::Err(_) => {
+                            error.wrong_variant.push(#name);
+                            ::std::option::Option::None
+                        }
+                    }
+                }
+            };
+            let take_values = fields.iter().zip(&locals).map(|(field, local)| {
+                let name = &field.external_name;
+                let ty = field.ty();
+                match field.attrs.flatten {
+                    true => {
+                        let build = build_flattened(ty, name);
+                        quote! {
+                            let #local = {
+                                let count = <#ty as #runtime::FieldNames>::FLATTENED_FIELD_COUNT;
+                                let values = ::std::iter::Iterator::collect::<::std::vec::Vec<E>>(
+                                    ::std::iter::Iterator::take(&mut *values, count),
+                                );
+                                if values.len() < count {
+                                    error.missing.push(#name);
+                                    ::std::option::Option::None
+                                } else {
+                                    #build
+                                }
+                            };
+                        }
+                    }
+                    false => quote! {
+                        let #local = match ::std::iter::Iterator::next(values) {
+                            ::std::option::Option::Some(value) => match ::std::convert::TryInto::<#ty>::try_into(value) {
+                                ::std::result::Result::Ok(value) => ::std::option::Option::Some(value),
+                                ::std::result::Result::Err(_) => {
+                                    error.wrong_variant.push(#name);
+                                    ::std::option::Option::None
+                                }
+                            },
+                            ::std::option::Option::None => {
+                                error.missing.push(#name);
+                                ::std::option::Option::None
+                            }
+                        };
+                    },
+                }
+            });
+            let build = quote! {
+                match (#(#locals,)*) {
+                    (#(::std::option::Option::Some(#locals),)*) if error.is_empty() => {
+                        ::std::result::Result::Ok(Self {
+                            #(#members: #locals,)*
+                            #(#skipped_members: #skipped_values,)*
+                        })
+                    }
+                    _ => ::std::result::Result::Err(error),
+                }
+            };
+            let by_name = derive_fields.then(|| {
+                // the values are only converted once they are all there, so that every field is
+                // reported once and in the order of the struct
+                let slots = fields.iter().zip(&locals).map(|(field, local)| match field.attrs.flatten {
+                    true => {
+                        let ty = field.ty();
+                        quote! {
+                            let mut #local: (::std::vec::Vec<::std::option::Option<E>>, bool) = (
+                                ::std::iter::Iterator::collect(::std::iter::Iterator::map(
+                                    0..<#ty as #runtime::FieldNames>::FLATTENED_FIELD_COUNT,
+                                    |_| ::std::option::Option::None,
+                                )),
+                                false,
+                            );
+                        }
+                    }
+                    false => quote! {
+                        let mut #local: (::std::option::Option<E>, bool) = (::std::option::Option::None, false);
+                    },
+                });
+                let (flattened_fields, value_fields): (Vec<_>, Vec<_>) = fields
+                    .iter()
+                    .zip(&locals)
+                    .partition(|(field, _)| field.attrs.flatten);
+                // the values of a flattened field are collected in the order of its own `fields`
+                let flattened_lookups = flattened_fields.iter().map(|(field, local)| {
+                    let ty = field.ty();
+                    let prefixes = field.flattened_prefixes(&separator);
+                    quote! {
+                        let index = [#(#prefixes),*]
+                            .iter()
+                            .find_map(|prefix| name.strip_prefix(prefix))
+                            .and_then(<#ty as #runtime::FieldNames>::flattened_field_index);
+                        if let ::std::option::Option::Some(index) = index {
+                            match &mut #local.0[index] {
+                                ::std::option::Option::Some(_) => #local.1 = true,
+                                slot => *slot = ::std::option::Option::Some(value),
+                            }
+                            continue;
+                        }
+                    }
+                });
+                let patterns = value_fields.iter().map(|(field, _)| field.name_pattern());
+                let value_locals = value_fields.iter().map(|(_, local)| local);
+                let takes = fields.iter().zip(&locals).map(|(field, local)| {
+                    let name = &field.external_name;
+                    let ty = field.ty();
+                    let take = match field.attrs.flatten {
+                        true => {
+                            let build = build_flattened(ty, name);
+                            quote! {
+                                match ::std::iter::Iterator::collect::<::std::option::Option<::std::vec::Vec<E>>>(
+                                    ::std::iter::IntoIterator::into_iter(#local.0),
+                                ) {
+                                    ::std::option::Option::Some(values) => #build,
+                                    ::std::option::Option::None => {
+                                        error.missing.push(#name);
+                                        ::std::option::Option::None
+                                    }
+                                }
+                            }
+                        }
+                        false => quote! {
+                            match #local.0 {
+                                ::std::option::Option::Some(value) => match ::std::convert::TryInto::<#ty>::try_into(value) {
+                                    ::std::result::Result::Ok(value) => ::std::option::Option::Some(value),
+                                    ::std::result::Result::Err(_) => {
+                                        error.wrong_variant.push(#name);
+                                        ::std::option::Option::None
+                                    }
+                                },
+                                ::std::option::Option::None => {
+                                    error.missing.push(#name);
+                                    ::std::option::Option::None
+                                }
+                            }
+                        },
+                    };
+                    quote! {
+                        let #local = if #local.1 {
+                            error.duplicated.push(#name);
+                            ::std::option::Option::None
+                        } else {
+                            #take
+                        };
+                    }
+                });
+                quote! {
+                    /// builds an Instance from the fields and values returned by `fields_and_values`,
+                    /// looking the fields up by their names and aliases
+                    pub fn from_fields_and_values<K, E>(
+                        fields_and_values: impl ::std::iter::IntoIterator<Item = (K, E)>,
+                    ) -> ::std::result::Result<Self, #error>
+                    where
+                    K: ::std::convert::AsRef<str>,
+                    #from_bounds
+                    {
+                        let mut error = <#error as ::std::default::Default>::default();
+                        #(#slots)*
+                        for (name, value) in fields_and_values {
+                            let name = ::std::convert::AsRef::<str>::as_ref(&name);
+                            #(#flattened_lookups)*
+                            match name.as_bytes() {
+                                #(#patterns => match &mut #value_locals.0 {
+                                    ::std::option::Option::Some(_) => #value_locals.1 = true,
+                                    slot => *slot = ::std::option::Option::Some(value),
+                                },)*
+                                _ => error.unknown.push(::std::borrow::ToOwned::to_owned(name)),
+                            }
+                        }
+                        #(#takes)*
+                        #build
+                    }
+
+                    /// builds an Instance from a map of the fields to their values, like a `HashMap` or a `BTreeMap`
+                    pub fn try_from_map<E>(
+                        map: impl ::std::iter::IntoIterator<Item = (::std::string::String, E)>,
+                    ) -> ::std::result::Result<Self, #error>
+                    where
+                    #from_bounds
+                    {
+                        Self::from_fields_and_values(map)
+                    }
+                }
+            });
+            let from_values_impl = runtime.as_ref().map(|runtime| {
+                let mut from_generics = generics::with_predicates(
+                    &generics,
+                    flattened_types.iter().map(|ty| -> WherePredicate {
+                        parse_quote!(#ty: #runtime::FromFieldValues<__E> + #runtime::FieldNames)
+                    }),
+                );
+                let predicates = &mut from_generics.make_where_clause().predicates;
+                predicates.push(parse_quote!(__E: #(::std::convert::TryInto<#value_types>)+*));
+                predicates.extend(default_bounds.iter().cloned());
+                from_generics.params.push(parse_quote!(__E));
+                let (impl_generics, _, where_clause) = from_generics.split_for_impl();
+                quote! {
+                    impl #impl_generics #runtime::FromFieldValues<__E> for #ident #ty_generics #where_clause {
+                        type Error = #error;
+
+                        fn from_values(
+                            values: impl ::std::iter::IntoIterator<Item = __E>,
+                        ) -> ::std::result::Result<Self, Self::Error> {
+                            Self::from_values(values)
+                        }
+                    }
+                }
+            });
+            Some(quote! {
+                /// The fields that kept values from being turned back into the Struct, a flattened
+                /// field reported as a whole
+                #[derive(Debug, Clone, Default, PartialEq, Eq)]
+                #vis struct #error {
+                    /// The fields without a value
+                    pub missing: ::std::vec::Vec<&'static str>,
+                    /// The fields given more than one value
+                    pub duplicated: ::std::vec::Vec<&'static str>,
+                    /// The names without a field, or the positions past the last one
+                    pub unknown: ::std::vec::Vec<::std::string::String>,
+                    /// The fields whose value can't be converted into their type
+                    pub wrong_variant: ::std::vec::Vec<&'static str>,
+                }
+                impl #error {
+                    /// returns if no field was at fault
+                    pub fn is_empty(&self) -> bool {
+                        self.missing.is_empty()
+                            && self.duplicated.is_empty()
+                            && self.unknown.is_empty()
+                            && self.wrong_variant.is_empty()
+                    }
+                }
+                impl ::std::fmt::Display for #error {
+                    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                        let problems = [
+                            ("missing", self.missing.join(", ")),
+                            ("duplicated", self.duplicated.join(", ")),
+                            ("unknown", self.unknown.join(", ")),
+                            ("of the wrong variant", self.wrong_variant.join(", ")),
+                        ];
+                        let problems = problems
+                            .iter()
+                            .filter(|(_, fields)| !fields.is_empty())
+                            .map(|(problem, fields)| ::std::format!("fields {problem}: {fields}"))
+                            .collect::<::std::vec::Vec<_>>();
+                        write!(f, "can't build `{}` from the values, {}", #struct_name, problems.join("; "))
+                    }
+                }
+                impl ::std::error::Error for #error {}
+
+                impl #impl_generics #ident #ty_generics #where_clause {
+                    /// builds an Instance from its values in the order `values` returns them
+                    pub fn from_values<E>(
+                        values: impl ::std::iter::IntoIterator<Item = E>,
+                    ) -> ::std::result::Result<Self, #error>
+                    where
+                    #from_bounds
+                    {
+                        let mut error = <#error as ::std::default::Default>::default();
+                        let values = &mut ::std::iter::IntoIterator::into_iter(values);
+                        #(#take_values)*
+                        for (position, _) in ::std::iter::Iterator::enumerate(values) {
+                            error.unknown.push(::std::string::ToString::to_string(&(#value_count + position)));
+                        }
+                        #build
+                    }
+
+                    #by_name
+                }
+
+                #from_values_impl
+            })
+        }
+        _ => None,
+    };
+    let struct_info = match (&runtime, derive_fields, &item_fields) {
+        (Some(runtime), true, DataFields::Struct(fields)) => {
+            let struct_name = ident.unraw().to_string();
+            let field_infos = fields.iter().map(|field| {
+                let name = &field.external_name;
+                let position = field.index;
+                let ty = field.ty();
+                let type_name = pretty_tokens(ty);
+                // only a type without generic parameters is known to be `'static`
+                let type_id = match generics::filter_generics(&generics, [ty]).params.is_empty() {
+                    true => quote! {
+                        ::std::option::Option::Some(::std::any::TypeId::of::<#ty> as fn() -> ::std::any::TypeId)
+                    },
+                    false => quote!(::std::option::Option::None),
+                };
+                let docs = docs(&field.field.attrs);
+                let visibility = pretty_tokens(&field.field.vis);
+                let has_builder_default = field.attrs.builder_default.is_some();
+                quote! {
+                    #runtime::FieldInfo {
+                        name: #name,
+                        position: #position,
+                        type_name: #type_name,
+                        type_id: #type_id,
+                        docs: #docs,
+                        visibility: #visibility,
+                        has_builder_default: #has_builder_default,
+                    }
+                }
+            });
+            Some(quote! {
+                impl #impl_generics #ident #ty_generics #where_clause {
+                    /// the description of the Struct and its fields
+                    pub const STRUCT_INFO: #runtime::StructInfo = #runtime::StructInfo {
+                        name: #struct_name,
+                        module_path: ::std::module_path!(),
+                        docs: #struct_docs,
+                        fields: &[#(#field_infos),*],
+                    };
+
+                    /// returns the descriptions of the Structs fields
+                    pub fn field_infos() -> &'static [#runtime::FieldInfo] {
+                        Self::STRUCT_INFO.fields
+                    }
+                }
+
+                impl #impl_generics #runtime::HasStructInfo for #ident #ty_generics #where_clause {
+                    const STRUCT_INFO: #runtime::StructInfo = Self::STRUCT_INFO;
+                }
+            })
+        }
+        _ => None,
+    };
+    let field_names = match item_fields {
+        DataFields::Struct(_) => quote!(Self::fields()),
+        DataFields::Enum(_) => quote!(self.variant_fields()),
+    };
     let fields_and_values_quote = match derive_fields && derive_values {
         true => Some(quote! {
-            impl #ident{
+            impl #impl_generics #ident #ty_generics #where_clause {
                 /**
 
                 returns a Vector of Tuples of the field and the values of this Instance
@@ -260,8 +1002,7 @@ pub fn derive_struct_iter_tools(input: TokenStream) -> TokenStream {
 
                 // Default just for demonstration
                 #[derive(StructIterTools, Default)]
-                #[StructFields]
-                #[StructValues]
+                #[struct_tools(fields, values)]
                 pub struct Foo{
                     field1: i32,
                     field2: String,
@@ -295,26 +1036,215 @@ pub fn derive_struct_iter_tools(input: TokenStream) -> TokenStream {
                  */
                 pub fn fields_and_values<E>(&self) -> ::std::vec::Vec<(::std::string::String, E)>
                 where
-                E: #types
+                #value_bounds
                 {
-                    let fields = Self::fields();
+                    let fields = #field_names;
                     let values = self.values();
                     let erg = fields.into_iter().zip(values).collect();
                     erg
                 }
+
+                /// returns the fields and values collected into a map, like a `HashMap` or a `BTreeMap`
+                pub fn to_map<M, E>(&self) -> M
+                where
+                M: ::std::iter::FromIterator<(::std::string::String, E)>,
+                #value_bounds
+                {
+                    ::std::iter::FromIterator::from_iter(self.fields_and_values::<E>())
+                }
             }
         }),
         false => None,
     };
+    let field_names_impl = match (&runtime, derive_fields, &item_fields) {
+        (Some(runtime), true, DataFields::Struct(_)) => {
+            let flattened_items = flattened.then(|| {
+                quote! {
+                    const FLATTENED_FIELD_COUNT: usize = Self::FLATTENED_FIELD_COUNT;
+
+                    fn flattened_field_index(name: &str) -> ::std::option::Option<usize> {
+                        Self::flattened_field_index(name)
+                    }
+                }
+            });
+            Some(quote! {
+                impl #impl_generics #runtime::FieldNames for #ident #ty_generics #where_clause {
+                    const FIELD_NAMES: &'static [&'static str] = Self::FIELD_NAMES;
+
+                    fn fields() -> ::std::vec::Vec<::std::string::String> {
+                        Self::fields()
+                    }
+
+                    fn field_index(name: &str) -> ::std::option::Option<usize> {
+                        Self::field_index(name)
+                    }
+
+                    #flattened_items
+                }
+            })
+        }
+        _ => None,
+    };
+    let field_values_impl = match (&runtime, derive_values) {
+        (Some(runtime), true) => {
+            let mut value_generics = generics::with_predicates(
+                &generics,
+                fields
+                    .iter()
+                    .map(|field| (field.attrs.flatten, field.ty()))
+                    .unique_by(|(_, ty)| *ty)
+                    .flat_map(|(flatten, ty)| -> Vec<WherePredicate> {
+                        match flatten {
+                            true => vec![parse_quote!(#ty: #runtime::FieldValues<__E>)],
+                            false => vec![
+                                parse_quote!(__E: ::std::convert::From<#ty>),
+                                clone_bound(ty),
+                            ],
+                        }
+                    }),
+            );
+            value_generics.params.push(parse_quote!(__E));
+            let (impl_generics, _, where_clause) = value_generics.split_for_impl();
+            let (_, ref_generics) = generics::with_lifetime(&generics, "a");
+            // the references the values convert from can't outlive the struct
+            let outlives: WherePredicate = parse_quote!(#ident #ty_generics: #lifetime);
+            let mut ref_generics = generics::with_predicates(
+                &ref_generics,
+                iter::once(outlives).chain(
+                    value_types
+                        .iter()
+                        .map(|ty| -> WherePredicate {
+                            parse_quote!(__E: ::std::convert::From<&#lifetime #ty>)
+                        })
+                        .chain(flattened_types.iter().map(|ty| -> WherePredicate {
+                            parse_quote!(#ty: #runtime::FieldValuesRef<#lifetime, __E>)
+                        })),
+                ),
+            );
+            ref_generics.params.push(parse_quote!(__E));
+            let (ref_impl_generics, _, ref_where_clause) = ref_generics.split_for_impl();
+            let into_values_impl = attrs.into.then(|| {
+                let mut into_generics = generics::with_predicates(
+                    &generics,
+                    value_types
+                        .iter()
+                        .map(|ty| -> WherePredicate { parse_quote!(__E: ::std::convert::From<#ty>) })
+                        .chain(flattened_types.iter().map(|ty| -> WherePredicate {
+                            parse_quote!(#ty: #runtime::IntoFieldValues<__E>)
+                        })),
+                );
+                into_generics.params.push(parse_quote!(__E));
+                let (impl_generics, _, where_clause) = into_generics.split_for_impl();
+                quote! {
+                    impl #impl_generics #runtime::IntoFieldValues<__E> for #ident #ty_generics #where_clause {
+                        fn into_values(self) -> ::std::vec::Vec<__E> {
+                            Self::into_values::<__E>(self)
+                        }
+                    }
+                }
+            });
+            Some(quote! {
+                impl #impl_generics #runtime::FieldValues<__E> for #ident #ty_generics #where_clause {
+                    fn values(&self) -> ::std::vec::Vec<__E> {
+                        Self::values::<__E>(self)
+                    }
+                }
+
+                impl #ref_impl_generics #runtime::FieldValuesRef<#lifetime, __E> for #ident #ty_generics #ref_where_clause {
+                    fn values_ref(&#lifetime self) -> ::std::vec::Vec<__E> {
+                        Self::values_ref::<__E>(self)
+                    }
+                }
+
+                #into_values_impl
+            })
+        }
+        _ => None,
+    };
+    let visit_fields = match (&runtime, derive_values) {
+        (Some(runtime), true) => {
+            // a flattened field hands its own fields on, their names prefixed with its own
+            let visit = |fields: &[(usize, &StructField, proc_macro2::TokenStream)],
+                         method: Ident| {
+                let visits = fields.iter().map(|(_, field, value)| {
+                    let name = &field.external_name;
+                    match field.attrs.flatten {
+                        true => {
+                            let prefix = format!("{name}{separator}");
+                            quote! {
+                                #runtime::VisitFields::#method(
+                                    #value,
+                                    &mut #runtime::PrefixedVisitor { prefix: #prefix, visitor: &mut *visitor },
+                                );
+                            }
+                        }
+                        false => quote!(visitor.visit(#name, #value);),
+                    }
+                });
+                quote!({ #(#visits)* })
+            };
+            let visit_fields =
+                item_fields.with_fields(|fields| visit(fields, format_ident!("visit_fields")));
+            let visit_fields_mut = item_fields
+                .with_fields_mut(|fields| visit(fields, format_ident!("visit_fields_mut")));
+            let static_generics =
+                generics::with_predicates(
+                    &generics,
+                    field_types
+                        .iter()
+                        .map(|ty| -> WherePredicate { parse_quote!(#ty: 'static) })
+                        .chain(flattened_types.iter().map(|ty| -> WherePredicate {
+                            parse_quote!(#ty: #runtime::VisitFields)
+                        })),
+                );
+            let static_where_clause = &static_generics.where_clause;
+            Some(quote! {
+                impl #impl_generics #ident #ty_generics #static_where_clause {
+                    /// calls `visitor` with the name and a reference to the value of every field
+                    pub fn visit_fields(&self, visitor: &mut impl #runtime::FieldVisitor) {
+                        #visit_fields
+                    }
+
+                    /// calls `visitor` with the name and a mutable reference to the value of every field
+                    pub fn visit_fields_mut(&mut self, visitor: &mut impl #runtime::FieldVisitorMut) {
+                        #visit_fields_mut
+                    }
+                }
+
+                impl #impl_generics #runtime::VisitFields for #ident #ty_generics #static_where_clause {
+                    fn visit_fields(&self, visitor: &mut impl #runtime::FieldVisitor) {
+                        Self::visit_fields(self, visitor)
+                    }
+
+                    fn visit_fields_mut(&mut self, visitor: &mut impl #runtime::FieldVisitorMut) {
+                        Self::visit_fields_mut(self, visitor)
+                    }
+                }
+            })
+        }
+        _ => None,
+    };
     let result = quote! {
+        #deprecations
+
         #fields_quote
 
         #values_quote
 
         #fields_and_values_quote
+
+        #from_values_quote
+
+        #field_names_impl
+
+        #struct_info
+
+        #field_values_impl
+
+        #visit_fields
     };
     //println!("{}",result);
-    result.into()
+    Ok(result)
 }
 
 /**
@@ -336,7 +1266,7 @@ you can just add the derive to it
 use struct_tools_derive::StructBuilder;
 
 #[derive(StructBuilder)]
-#[StructFields]
+#[struct_tools(fields)]
 pub struct Foo{
     field1: i32,
     field2: String,
@@ -403,11 +1333,11 @@ If you want specific fields to have specific Default-values you can add the defa
 use struct_tools_derive::{StructFieldEnum, StructBuilder};
 
 #[derive(StructFieldEnum, StructBuilder)]
-#[StructFields]
+#[struct_tools(fields)]
 pub struct Foo{
-    #[builder_default(1)]
+    #[struct_tools(builder(default = 1))]
     field1: i32,
-    #[builder_default("Hello".to_owned())]
+    #[struct_tools(builder(default = "Hello".to_owned()))]
     field2: String,
     //{...}
 }
@@ -417,88 +1347,74 @@ TODO!
 */
 #[proc_macro_derive(
     StructBuilderOld,
-    attributes(StructFields, BuilderDerive, builder_default)
+    attributes(struct_tools, StructFields, BuilderDerive, builder_default)
 )]
 pub fn derive_struct_builder(input: TokenStream) -> TokenStream {
-    let DeriveInput {
-        attrs, ident, data, ..
-    } = parse_macro_input!(input as DeriveInput);
-
-    let new_ident = Ident::new(&(ident.to_string() + "Builder"), ident.span());
-    let error = Ident::new(&(new_ident.to_string() + "Error"), new_ident.span());
-
-    let attr_strings: Vec<String> = attrs
-        .iter()
-        .filter_map(|attr| attr.path().get_ident())
-        .map(|attr| attr.to_string())
-        .collect();
-    //println!("{:#?}; {:#?}",attr_strings, attrs);
-
-    let get_fields = attr_strings.contains(&"StructFields".to_owned());
-    let get_derives = attr_strings.contains(&"BuilderDerive".to_owned());
-
-    let derives = match get_derives {
-        true => {
-            let iter = attrs
-                .iter()
-                .filter(|attr| attr.path().is_ident("BuilderDerive"))
-                .collect_vec();
-            Some(quote!(#[derive #(#iter),*]))
-        }
-        false => None,
-    };
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_struct_builder(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
 
-    let fields = match data {
-        Struct(DataStruct {
-            fields: Named(FieldsNamed { ref named, .. }),
-            ..
-        }) => named,
-        _ => todo!(),
-    };
+fn expand_struct_builder(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let DeriveInput {
+        attrs,
+        vis,
+        ident,
+        data,
+        generics,
+    } = input;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let attrs = ContainerAttrs::parse(&attrs)?;
+    let vis = attrs.item_vis(&attrs.builder_vis, &vis);
+
+    let new_ident = generated_name(&attrs.builder, &ident, "Builder");
+    let error = generated_name(&attrs.builder_error, &new_ident, "Error");
+
+    let get_fields = attrs.fields;
+    let derives = derive_attribute(&attrs.builder_derive);
+
+    let fields = struct_fields(&data, &attrs, "StructBuilderOld")?;
+    let deprecations = deprecations(
+        &attrs,
+        &fields,
+        &["StructFields", "BuilderDerive", "builder_default"],
+    );
+    let (skipped, fields): (Vec<_>, Vec<_>) = fields
+        .into_iter()
+        .partition(|field| field.attrs.skip.builder);
+    let (skipped_members, skipped_values) = skipped_fields(&skipped);
     //println!("{:#?}",fields);
 
     let field_defaults = fields
         .iter()
-        .cloned()
         .map(|field| {
-            let defaults = field
-                .attrs
-                .iter()
-                .cloned()
-                .filter(|attr| attr.path().is_ident("builder_default"))
-                .flat_map(|attr| attr.parse_args::<proc_macro2::TokenStream>())
-                .collect_vec();
-            (field.ident.unwrap(), defaults)
-        })
-        .map(|(field, attrs)| match attrs.is_empty() {
-            true => quote!(#field: None),
-            false => {
-                let default = attrs[0].clone();
-                quote!(#field: Some(#default))
-            }
+            let field_ident = &field.ident;
+            Ok(match &field.attrs.builder_default {
+                None => quote!(#field_ident: None),
+                Some(default) => quote!(#field_ident: Some(#default)),
+            })
         })
-        .collect_vec();
+        .collect::<syn::Result<Vec<_>>>()?;
     //println!("{:?}", field_defaults);
 
-    let field_types = fields.iter().cloned().map(|field| field.ty).collect_vec();
-    let field_names = fields
-        .iter()
-        .cloned()
-        .flat_map(|field| field.ident)
-        .collect_vec();
+    let field_types = fields.iter().map(|field| field.ty()).collect_vec();
+    let field_names = fields.iter().map(|field| &field.ident).collect_vec();
+    let field_members = fields.iter().map(|field| &field.member).collect_vec();
 
-    let set = field_names
+    let set = fields
         .iter()
-        .cloned()
-        .map(|ident| Ident::new(&("set_".to_owned() + &ident.to_string()), ident.span()))
+        .map(|field| format_ident!("set_{}", field.name, span = field.ident.span()))
         .collect_vec();
 
     let result = match get_fields {
         true => quote! {
+            #deprecations
 
             #[allow(non_camel_case_types)]
             #[derive(Debug)]
-            pub enum #error {
+            #vis enum #error {
 
                 #(#field_names),*
 
@@ -511,18 +1427,20 @@ pub fn derive_struct_builder(input: TokenStream) -> TokenStream {
             impl ::std::error::Error for #error {}
 
             #derives
-            pub struct #new_ident{
-                #(#field_names : Option< #field_types >),*
+            #vis struct #new_ident #impl_generics #where_clause {
+                #(#field_names : Option< #field_types >,)*
+                __marker: ::std::marker::PhantomData<fn() -> #ident #ty_generics>,
             }
-            impl Default for #new_ident{
+            impl #impl_generics Default for #new_ident #ty_generics #where_clause {
                 fn default() -> Self {
                     Self {
-                        #( #field_defaults ),*
+                        #( #field_defaults, )*
+                        __marker: ::std::marker::PhantomData,
                     }
                 }
             }
-            impl #new_ident{
-                pub fn build(self) -> Result< #ident , ::std::vec::Vec< #error > > {
+            impl #impl_generics #new_ident #ty_generics #where_clause {
+                pub fn build(self) -> Result< #ident #ty_generics, ::std::vec::Vec< #error > > {
 
                     let mut errors: ::std::vec::Vec< #error > = ::std::vec::Vec::new();
 
@@ -534,22 +1452,28 @@ pub fn derive_struct_builder(input: TokenStream) -> TokenStream {
                     match errors.is_empty(){
                         true => Ok(
                                     #ident {
-                                        #(#field_names: self. #field_names .unwrap()),*
+                                        #(#field_members: self. #field_names .unwrap(),)*
+                                        #(#skipped_members: #skipped_values,)*
                                     }
                                 ),
                         false => Err(errors)
                     }
                 }
-                #(pub fn #set (mut self, #field_names: #field_types) -> #new_ident {
+                #(pub fn #set (mut self, #field_names: #field_types) -> Self {
                     self. #field_names = Some( #field_names );
                     self
                 })*
             }
         },
-        false => panic!("Attribute: \"StructFields\" is not set"),
+        false => {
+            return Err(syn::Error::new(
+                ident.span(),
+                "the `#[struct_tools(fields)]` attribute is required to derive a builder",
+            ))
+        }
     };
     //println!("{result}");
-    result.into()
+    Ok(result)
 }
 
 /**
@@ -571,7 +1495,7 @@ you can just add the derive to it
 use struct_tools_derive::StructBuilder;
 
 #[derive(StructBuilder)]
-#[StructFields]
+#[struct_tools(fields)]
 pub struct Foo{
     field1: i32,
     field2: String,
@@ -581,6 +1505,8 @@ pub struct Foo{
 
 This Grants you access to an automatically generated struct with the name `{structname}Builder`.
 
+The setters are named after the fields, `set_0`, `set_1`, ... for tuple structs.
+
 ```rust
 
 # pub struct Foo{
@@ -603,7 +1529,7 @@ impl Default for FooBuilder<NoFIELD1, NoFIELD2/*,... */> {
         }
     }
 }
-impl<A, B/*,... */> FooBuilder<A, B,/*,... */> 
+impl<A, B/*,... */> FooBuilder<A, B,/*,... */>
 where
     A: FIELD1,
     B: FIELD2,
@@ -700,11 +1626,11 @@ If you want specific fields to have specific Default-values you can add the defa
 use struct_tools_derive::{StructBuilder};
 
 #[derive(StructBuilder)]
-#[StructFields]
+#[struct_tools(fields)]
 pub struct Foo{
-    #[builder_default(1)]
+    #[struct_tools(builder(default = 1))]
     field1: i32,
-    #[builder_default("Hello".to_owned())]
+    #[struct_tools(builder(default = "Hello".to_owned()))]
     field2: String,
     //{...}
 }
@@ -714,73 +1640,90 @@ TODO!
 */
 #[proc_macro_derive(
     StructBuilder,
-    attributes(StructFields, BuilderDerive, builder_default)
+    attributes(struct_tools, StructFields, BuilderDerive, builder_default)
 )]
 pub fn derive_struct_builder_type_state(input: TokenStream) -> TokenStream {
-    let DeriveInput {
-        attrs, ident, data, ..
-    } = parse_macro_input!(input as DeriveInput);
-
-    let attr_strings: Vec<String> = attrs
-        .iter()
-        .filter_map(|attr| attr.path().get_ident())
-        .map(|attr| attr.to_string())
-        .collect_vec();
-    // dbg!(&attr_strings);
-
-    let new_ident = Ident::new((ident.to_string() + "Builder").as_str(), ident.span());
-
-    let get_fields = attr_strings.contains(&"StructFields".to_owned());
-    let get_derives = attr_strings.contains(&"BuilderDerive".to_owned());
-
-    let derives = match get_derives {
-        true => {
-            let iter = attrs
-                .iter()
-                .filter(|attr| attr.path().is_ident("BuilderDerive"))
-                .collect_vec();
-            Some(quote!(#[derive #(#iter),*]))
-        }
-        false => None,
-    };
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_struct_builder_type_state(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
 
-    let fields = match data {
-        Struct(DataStruct {
-            fields: Named(FieldsNamed { ref named, .. }),
-            ..
-        }) => named,
-        _ => panic!("Builder only available on non Tuple or Unit Structs"),
-    };
+fn expand_struct_builder_type_state(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let DeriveInput {
+        attrs,
+        vis,
+        ident,
+        data,
+        generics,
+    } = input;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let attrs = ContainerAttrs::parse(&attrs)?;
+    let vis = attrs.item_vis(&attrs.builder_vis, &vis);
+
+    let new_ident = generated_name(&attrs.builder, &ident, "Builder");
+
+    let get_fields = attrs.fields;
+    let derives = derive_attribute(&attrs.builder_derive);
+
+    let fields = struct_fields(&data, &attrs, "StructBuilder")?;
+    let deprecations = deprecations(
+        &attrs,
+        &fields,
+        &["StructFields", "BuilderDerive", "builder_default"],
+    );
+    let (skipped, fields): (Vec<_>, Vec<_>) = fields
+        .into_iter()
+        .partition(|field| field.attrs.skip.builder);
+    let (skipped_members, skipped_values) = skipped_fields(&skipped);
     //dbg!(&fields);
 
-    let field_types = fields.iter().cloned().map(|field| field.ty).collect_vec();
+    // the struct's own generics come first, the state of every field follows
+    let struct_params = generics::params_without_defaults(&generics);
+    let struct_params = quote!(#(#struct_params,)*);
+    let struct_args = generics::param_args(&generics);
+    let struct_args = quote!(#(#struct_args,)*);
+
+    let field_types = fields.iter().map(|field| field.ty()).collect_vec();
     let field_traits = fields
         .iter()
-        .cloned()
         .map(|field| {
-            let strings = field.ident.unwrap().to_string();
-            Ident::new(&strings.to_uppercase(), proc_macro2::Span::call_site())
+            let strings = field.ident.unraw().to_string().to_uppercase();
+            parse_ident(&strings).ok_or_else(|| {
+                syn::Error::new(
+                    field.ident.span(),
+                    format!("the generated trait name `{strings}` is not a valid identifier"),
+                )
+            })
         })
+        .collect::<syn::Result<Vec<_>>>()?;
+    // the Some{FIELD} structs only carry the generics their field's type needs
+    let field_generics = field_types
+        .iter()
+        .map(|ty| generics::filter_generics(&generics, [*ty]))
         .collect_vec();
     let (field_structs_quote, (some_field_structs, _no_field_structs)): (Vec<_>, (Vec<_>, Vec<_>)) =
         field_traits
             .iter()
             .cloned()
             .zip(field_types.iter())
-            .map(|(ident, ty)| {
+            .zip(field_generics.iter())
+            .map(|((ident, ty), generics)| {
                 let some_ident = format_ident!("Some{}", ident);
                 let no_ident = format_ident!("No{}", ident);
+                let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
                 (
                     quote! {
                             #[allow(non_camel_case_types)]
-                            pub struct #some_ident(#ty);
-                            impl From<#ty> for #some_ident{
+                            #vis struct #some_ident #impl_generics (#ty) #where_clause;
+                            impl #impl_generics From<#ty> for #some_ident #ty_generics #where_clause {
                                 fn from(value: #ty) -> Self {
                                     #some_ident(value)
                                 }
                             }
-                            impl #ident for #some_ident{}
-                            impl Some for #some_ident{
+                            impl #impl_generics #ident for #some_ident #ty_generics #where_clause {}
+                            impl #impl_generics Some for #some_ident #ty_generics #where_clause {
                                 type Output = #ty;
 
                                 fn get(self) -> Self::Output {
@@ -789,160 +1732,152 @@ pub fn derive_struct_builder_type_state(input: TokenStream) -> TokenStream {
                             }
 
                             #[allow(non_camel_case_types)]
-                            pub struct #no_ident;
+                            #vis struct #no_ident;
                             impl #ident for #no_ident{}
                     },
-                    (some_ident, no_ident),
+                    (quote!(#some_ident #ty_generics), no_ident),
                 )
             })
             .unzip();
-    let generics = ('A'..='Z')
-        .map(|chr| Ident::new(&chr.to_string(), proc_macro2::Span::call_site()))
-        .enumerate()
-        .filter_map(|(i, ident)| match i < field_traits.len() {
-            true => Some(ident),
-            false => None,
-        })
+    let states = (0..field_traits.len())
+        .map(|i| format_ident!("__S{}", i))
         .collect_vec();
-    let field_names = fields
+    let field_names = fields.iter().map(|field| &field.ident).collect_vec();
+    let field_members = fields.iter().map(|field| &field.member).collect_vec();
+    let mut field_defaults = Vec::new();
+    let mut default_states = Vec::new();
+    for ((field, ty), some_struct) in fields
         .iter()
-        .cloned()
-        .flat_map(|field| field.ident)
-        .collect_vec();
-    let (field_defaults, default_generics): (Vec<_>, Vec<_>) = fields
-        .iter()
-        .cloned()
         .zip(field_traits.iter())
-        .map(|(field, ident)| {
-            let defaults = field
-                .attrs
-                .iter()
-                .cloned()
-                .filter(|attr| attr.path().is_ident("builder_default"))
-                .flat_map(|attr| attr.parse_args::<proc_macro2::TokenStream>())
-                .collect_vec();
-            (field.ident.unwrap(), /*field.ty*/ ident, defaults)
-        })
-        .map(|(field, ty, attrs)| match attrs.is_empty() {
-            true => {
-                let typ = Ident::new(&("No".to_owned() + &ty.to_string()), field.span());
-                (quote!(#field: #typ), quote!(#typ))
+        .zip(some_field_structs.iter())
+    {
+        let field_ident = &field.ident;
+        match &field.attrs.builder_default {
+            None => {
+                let typ = format_ident!("No{}", ty, span = field_ident.span());
+                field_defaults.push(quote!(#field_ident: #typ));
+                default_states.push(quote!(#typ));
             }
-            false => {
-                let default = attrs[0].clone();
-                let typ = Ident::new(&("Some".to_owned() + &ty.to_string()), field.span());
-                (quote!(#field: #typ (#default)), quote!(#typ))
+            Some(default) => {
+                let typ = format_ident!("Some{}", ty, span = field_ident.span());
+                field_defaults.push(quote!(#field_ident: #typ (#default)));
+                default_states.push(quote!(#some_struct));
             }
-        })
-        .unzip();
+        }
+    }
     //dbg!(&field_defaults);
 
-    let set = field_names
+    let set = fields
         .iter()
-        .cloned()
-        .map(|ident| {
-            Ident::new(
-                &("custom_set_".to_owned() + &ident.to_string()),
-                ident.span(),
-            )
-        })
+        .map(|field| format_ident!("custom_set_{}", field.name, span = field.ident.span()))
         .collect_vec();
 
-    let pre_set = field_names
+    let pre_set = fields
         .iter()
-        .cloned()
-        .map(|ident| Ident::new(&("set_".to_owned() + &ident.to_string()), ident.span()))
+        .map(|field| format_ident!("set_{}", field.name, span = field.ident.span()))
         .collect_vec();
 
     let set_vars = quote! {#(let #field_names = self. #field_names);*};
     let return_set = quote! {#new_ident {
-        #(#field_names),*
+        #(#field_names,)*
+        __marker: ::std::marker::PhantomData,
     }};
-    let set_generics = (0..fields.len())
+    let set_states = (0..fields.len())
         .map(|i| {
-            let mut new_generics = generics.clone();
-            if let Some(change) = new_generics.get_mut(i) {
-                *change = Ident::new("T", change.span());
+            let mut new_states = states.iter().map(|state| quote!(#state)).collect_vec();
+            if let Some(change) = new_states.get_mut(i) {
+                *change = quote!(__T);
             };
 
-            quote!(#(#new_generics),*)
+            quote!(#(#new_states),*)
         })
         .collect_vec();
-    let pre_set_generics = some_field_structs
+    let pre_set_states = some_field_structs
         .iter()
-        .cloned()
         .enumerate()
-        .map(|(i, ident)| {
-            let mut new_generics = generics.clone();
-            if let Some(change) = new_generics.get_mut(i) {
-                *change = ident;
+        .map(|(i, some_struct)| {
+            let mut new_states = states.iter().map(|state| quote!(#state)).collect_vec();
+            if let Some(change) = new_states.get_mut(i) {
+                *change = some_struct.clone();
             };
 
-            quote!(#(#new_generics),*)
+            quote!(#(#new_states),*)
         })
         .collect_vec();
 
     let result = match get_fields {
         true => quote! {
+            #deprecations
 
-            pub trait Some {
+            #vis trait Some {
                 type Output;
                 fn get(self) -> Self::Output;
             }
 
             #(
                 #[allow(non_camel_case_types)]
-                pub trait #field_traits{}
+                #vis trait #field_traits{}
             )*
 
             #(#field_structs_quote)*
 
             #derives
-            pub struct #new_ident<#(#generics: #field_traits),*>{
-                #(#field_names : #generics),*
+            #vis struct #new_ident<#struct_params #(#states: #field_traits),*> #where_clause {
+                #(#field_names : #states,)*
+                __marker: ::std::marker::PhantomData<fn() -> #ident #ty_generics>,
             }
-            impl Default for #new_ident<#(#default_generics),*>{
+            impl #impl_generics Default for #new_ident<#struct_args #(#default_states),*> #where_clause {
                 fn default() -> Self {
                     Self {
-                        #( #field_defaults ),*
+                        #( #field_defaults, )*
+                        __marker: ::std::marker::PhantomData,
                     }
                 }
             }
-            impl <#(#generics: #field_traits),*> #new_ident<#(#generics),*> {
+            impl <#struct_params #(#states: #field_traits),*> #new_ident<#struct_args #(#states),*> #where_clause {
                 #(
-                    pub fn #set<T> (self, value: T) -> #new_ident<#set_generics>
-                    where T : #field_traits + Some{
+                    pub fn #set<__T> (self, value: __T) -> #new_ident<#struct_args #set_states>
+                    where __T : #field_traits + Some{
                         #set_vars ;
                         let #field_names = value;
                         #return_set
                     }
                 )*
                 #(
-                    pub fn #pre_set(self, value: impl Into< #some_field_structs >) -> #new_ident<#pre_set_generics> {
+                    pub fn #pre_set(self, value: impl Into< #some_field_structs >) -> #new_ident<#struct_args #pre_set_states> {
                         #set_vars ;
                         let #field_names = value.into();
                         #return_set
                     }
                 )*
             }
-            impl<#(#generics : #field_traits + Some<Output= #field_types>),*> #new_ident<#(#generics),*> {
-                pub fn build(self) ->  #ident {
+            impl<#struct_params #(#states : #field_traits + Some<Output= #field_types>),*> #new_ident<#struct_args #(#states),*> #where_clause {
+                pub fn build(self) ->  #ident #ty_generics {
 
                     #ident{
-                        #(#field_names: self. #field_names .get()),*
+                        #(#field_members: self. #field_names .get(),)*
+                        #(#skipped_members: #skipped_values,)*
                     }
                 }
             }
         },
-        false => panic!("Attribute: \"StructFields\" is not set"),
+        false => {
+            return Err(syn::Error::new(
+                ident.span(),
+                "the `#[struct_tools(fields)]` attribute is required to derive a builder",
+            ))
+        }
     };
-    println!("{result}");
-    result.into()
+    //println!("{result}");
+    Ok(result)
 }
 
 /**
 Will create an Enum which is capable of containing all possible contents of the struct
 
+Derived for an enum, it contains the contents of every variant, and the values are taken from the
+active variant.
+
 # Example
 
 ```rust
@@ -958,7 +1893,12 @@ pub struct Foo{
 
 This Grants you access to an automatically generated Enum with the name `{structname}Enum`.
 
-its Variants are named by Capitalizing the first letter of the respective Type
+its Variants are named after the respective Type in PascalCase, leaving out lifetimes and
+modules, e.g. `VecString` for `Vec<String>`, `RefStr` for `&'a str`, `TupleU8U8` for `(u8, u8)`,
+`Array4U8` for `[u8; 4]` and `FnU8ToBool` for `fn(u8) -> bool`.
+
+Two Types that would share a name, like `a::Length` and `b::Length`, are an error, and a field can
+name the variant of its Type itself with `#[struct_tools(variant = "...")]`.
 
 ```rust
 pub enum FooEnum{
@@ -982,132 +1922,352 @@ impl From<String> for FooEnum {
 
 This means, that it can be used with both values and fields and values from StructIterTools
 
-you can also have it derive traits by adding them to the `EnumDerives` attribute like this:
+Going back, every Type implements `TryFrom<FooEnum>` and `&'a T` implements `TryFrom<&'a FooEnum>`.
+They fail with a generated `FooEnumError`, which names the `expected` variant and the `actual` one
+holding the value:
+
+```rust
+use struct_tools_derive::StructEnum;
+
+#[derive(StructEnum)]
+pub struct Foo{
+    field1: i32,
+    field2: String,
+}
+
+assert_eq!(i32::try_from(FooEnum::I32(4)), Ok(4));
+assert_eq!(<&i32>::try_from(&FooEnum::I32(4)), Ok(&4));
+
+let error = i32::try_from(FooEnum::String(String::new())).unwrap_err();
+assert_eq!(error, FooEnumError{ expected: "I32", actual: Some("String") });
+assert_eq!(error.to_string(), "expected the variant `I32`, found `String`");
+```
+
+Its variants get the methods `is_*`, `as_*`, `as_*_mut`, `into_*` and `expect_*`, named after them
+in snake_case, which `StructEnumMut` generates for its variants as well:
+
+```rust
+# use struct_tools_derive::StructEnum;
+#[derive(StructEnum)]
+pub struct Foo{
+    field1: i32,
+    field2: String,
+}
+
+let mut value = FooEnum::String(String::from("two"));
+assert!(value.is_string());
+value.as_string_mut().unwrap().push('!');
+assert_eq!(value.as_i32(), None);
+assert_eq!(value.into_string(), Some(String::from("two!")));
+```
+
+`#[struct_tools(value_enum = path::to::Enum)]` wraps the fields in an existing enum with these
+variants instead, for example one shared by many structs, which doesn't get these methods. A
+field whose type already has its `From` and `TryFrom` impls, because another struct generated
+them or the type is an alias of another field's type, is marked `#[struct_tools(skip(from))]`:
+
+```rust
+use struct_tools_derive::StructEnum;
+
+pub type Id = u64;
+
+#[derive(Debug, PartialEq)]
+pub enum Value{
+    U64(u64),
+    String(String),
+}
+
+#[derive(StructEnum)]
+#[struct_tools(value_enum = Value)]
+pub struct Foo{
+    #[struct_tools(skip(from))]
+    id: Id,
+    count: u64,
+}
+
+#[derive(StructEnum)]
+#[struct_tools(value_enum = Value)]
+pub struct Bar{
+    name: String,
+}
+
+let foo = Foo{ id: 1, count: 2 };
+assert_eq!(foo.gets_enums(), vec![Value::U64(1), Value::U64(2)]);
+```
+
+you can also have it derive traits by adding them to `enum_derive(...)` in the `struct_tools` attribute like this:
 
 ```rust
 # use struct_tools_derive::{StructEnum, StructIterTools};
 #[derive(StructEnum)]
-#[EnumDerive(Debug)]
+#[struct_tools(enum_derive(Debug))]
 pub struct Foo{
     field1: i32,
     field2: String,
     //{...}
 }
 */
-#[proc_macro_derive(StructEnum, attributes(EnumDerive))]
+#[proc_macro_derive(StructEnum, attributes(struct_tools, EnumDerive))]
 pub fn derive_struct_enum(input: TokenStream) -> TokenStream {
-    let ast = parse_macro_input!(input as DeriveInput);
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_struct_enum(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_struct_enum(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
     let DeriveInput {
-        attrs, ident, data, ..
-    } = ast;
+        attrs,
+        vis,
+        ident,
+        data,
+        generics,
+    } = input;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     //println!("{attrs:?}\n");
 
-    let attr: Vec<Attribute> = attrs
-        .into_iter()
-        .filter(|attr| attr.path().is_ident("EnumDerive"))
-        .collect();
-    //println!("{attr:?}\n");
+    let attrs = ContainerAttrs::parse(&attrs)?;
+    let vis = attrs.item_vis(&attrs.enum_vis, &vis);
+    let derives = derive_attribute(&attrs.enum_derive);
 
     let old_ident = ident.clone();
 
-    let ident = Ident::new(&(ident.to_string() + "Enum"), ident.span());
+    let ident = generated_name(&attrs.type_enum, &ident, "Enum");
+    let method = attrs
+        .enum_method
+        .clone()
+        .unwrap_or_else(|| format_ident!("gets_enums"));
 
-    let derives = match attr.is_empty() {
-        false => Some(
-            attr.into_iter()
-                .flat_map(|attr| attr.parse_args::<proc_macro2::TokenStream>()),
-        ),
-        true => None,
-    };
-    let derives = derives.map(|iter| quote! {#[derive (#(#iter),*)]});
-
-    let fields = match data {
-        Struct(DataStruct {
-            fields: Named(FieldsNamed { ref named, .. }),
-            ..
-        }) => named,
-        _ => todo!(),
-    };
+    let item_fields = data_fields(&data, &attrs, "StructEnum")?;
+    let deprecations = deprecations(&attrs, item_fields.all(), &["EnumDerive"]);
+    let item_fields = item_fields.without(|field| field.attrs.skip.enums);
+    let fields = item_fields.all();
 
     let field_types = fields
         .iter()
-        .map(|field| &field.ty)
+        .map(|field| field.ty())
         .unique()
         .collect::<Vec<&Type>>();
 
-    let struct_fields = fields.iter().flat_map(|field| &field.ident).collect_vec();
-    let enum_fields = field_types
+    let enum_fields = type_variants(&field_types, fields.iter().copied())?;
+    let variant_of =
+        |ty: &Type| &enum_fields[field_types.iter().position(|other| *other == ty).unwrap()];
+
+    let enum_generics = generics::filter_generics(&generics, field_types.iter().copied());
+    let (enum_impl_generics, enum_ty_generics, enum_where_clause) = enum_generics.split_for_impl();
+
+    // the enum the fields are wrapped in, which `value_enum` can point at an existing one
+    let target = match &attrs.value_enum {
+        Some(path) => quote!(#path),
+        None => quote!(#ident #enum_ty_generics),
+    };
+    let wrap = |ty: &Type, value: proc_macro2::TokenStream| match &attrs.value_enum {
+        Some(path) => quote!(<#path as ::std::convert::From<#ty>>::from(#value)),
+        None => {
+            let variant = variant_of(ty);
+            quote!(#ident::#variant(#value))
+        }
+    };
+    let values = item_fields.collect_values(|_, field, value| {
+        wrap(field.ty(), quote!(::std::clone::Clone::clone(#value)))
+    });
+    // only with `into`, see `derive_struct_iter_tools`
+    let into_values = attrs.into.then(|| {
+        item_fields.with_owned_fields(|fields| {
+            let values = fields
+                .iter()
+                .map(|(_, field, value)| wrap(field.ty(), value.clone()));
+            quote!(::std::vec![#(#values),*])
+        })
+    });
+
+    // `skip(from)` leaves out the conversions of a type that already has them, e.g. because it is
+    // an alias of another type or another struct shares the `value_enum`
+    let skip_from = |ty: &Type| {
+        fields
+            .iter()
+            .any(|field| field.ty() == ty && field.attrs.skip.from)
+    };
+    // an existing enum can't hold the generic types of the struct, so it has to convert them itself
+    let is_generic = |ty: &Type| !generics::filter_generics(&generics, [ty]).params.is_empty();
+    let (converts_from, converts_into): (Vec<bool>, Vec<bool>) = field_types
         .iter()
-        .cloned()
-        .map(|typ| {
-            let string = typ
-                .to_token_stream()
-                .to_string()
-                .char_indices()
-                .map(|(i, chr)| {
-                    if i == 0 {
-                        chr.to_uppercase().to_string()
-                    //} else if vec!['<','>','(',')','[',']'].contains(&chr){
-                    //    String::new()
-                    } else if !chr.is_ascii_alphabetic() && !chr.is_ascii_alphanumeric() {
-                        String::new()
-                    } else {
-                        chr.to_string()
-                    }
-                })
-                .collect::<String>();
-            let string = string.replace(' ', "");
-            let string = string.replace('(', "");
-            let string = string.replace(')', "");
-            Ident::new(&string, Span::call_site().into())
+        .map(|ty| match &attrs.value_enum {
+            _ if skip_from(ty) => (false, false),
+            Some(_) => (!is_generic(ty), !is_generic(ty)),
+            None => generics::converts(&generics, &field_types, ty),
         })
-        .collect::<Vec<Ident>>();
-    let from_fields = enum_fields.clone();
-    let from_types = field_types.clone();
+        .unzip();
+    let (from_fields, from_types): (Vec<_>, Vec<&&Type>) = enum_fields
+        .iter()
+        .zip(field_types.iter())
+        .zip(&converts_from)
+        .filter_map(|(field, converts)| converts.then_some(field))
+        .unzip();
+    let (try_fields, try_types): (Vec<_>, Vec<&&Type>) = enum_fields
+        .iter()
+        .zip(field_types.iter())
+        .zip(&converts_into)
+        .filter_map(|(field, converts)| converts.then_some(field))
+        .unzip();
+    let (conversion_generics, conversion_where_clause) = match &attrs.value_enum {
+        Some(_) => (None, None),
+        None => (Some(&enum_impl_generics), enum_where_clause),
+    };
+    let value_bounds = match &attrs.value_enum {
+        Some(path) => field_types
+            .iter()
+            .filter(|ty| is_generic(ty))
+            .map(|ty| -> WherePredicate { parse_quote!(#path: ::std::convert::From<#ty>) })
+            .collect_vec(),
+        None => Vec::new(),
+    };
+    let clone_bounds = field_types.iter().map(|ty| clone_bound(ty));
+    let clone_bounds = quote!(#(#clone_bounds,)* #(#value_bounds,)*);
 
-    let result = quote! {
-        #derives
-        pub enum #ident {
-            #(#enum_fields (#field_types)),*
+    let runtime_impl = runtime_crate(&attrs).map(|runtime| {
+        let clone_generics = generics::with_predicates(
+            &generics,
+            field_types
+                .iter()
+                .map(|ty| clone_bound(ty))
+                .chain(value_bounds.iter().cloned()),
+        );
+        let where_clause = &clone_generics.where_clause;
+        quote! {
+            impl #impl_generics #runtime::HasTypeEnum for #old_ident #ty_generics #where_clause {
+                type TypeEnum = #target;
+
+                fn gets_enums(&self) -> ::std::vec::Vec<Self::TypeEnum> {
+                    Self::#method(self)
+                }
+            }
+        }
+    });
+    let type_enum = match &attrs.value_enum {
+        Some(_) => None,
+        None => {
+            let accessors = variant_accessors(&enum_fields, &field_types, None)?;
+            Some(quote! {
+                #derives
+                #vis enum #ident #enum_impl_generics #enum_where_clause {
+                    #(#enum_fields (#field_types)),*
+                }
+
+                impl #enum_impl_generics #ident #enum_ty_generics #enum_where_clause {
+                    #accessors
+                }
+            })
+        }
+    };
+    let value_generics = generics::with_predicates(&generics, value_bounds.iter().cloned());
+    let into_where_clause = &value_generics.where_clause;
+
+    // converting back out of the enum fails with an error naming both variants
+    let error = generated_name(&attrs.enum_error, &old_ident, "EnumError");
+    let enum_error = match field_types.is_empty() {
+        true => None,
+        false => Some(enum_error(vis, &error)),
+    };
+    let enum_path = match &attrs.value_enum {
+        Some(path) => quote!(#path),
+        None => quote!(#ident),
+    };
+    let try_names = try_fields
+        .iter()
+        .map(|field| field.to_string())
+        .collect_vec();
+    // of an existing enum only the variants converted into are known to exist
+    let actual = match &attrs.value_enum {
+        Some(_) => variant_name(&enum_path, try_fields.iter().copied(), true),
+        None => variant_name(&enum_path, &enum_fields, false),
+    };
+    let ref_generics = match &attrs.value_enum {
+        Some(_) => Generics::default(),
+        None => enum_generics.clone(),
+    };
+    let (lifetime, ref_generics) = generics::with_lifetime(&ref_generics, "a");
+    let (ref_impl_generics, _, _) = ref_generics.split_for_impl();
+
+    let into_enums = into_values.map(|into_values| {
+        quote! {
+            impl #impl_generics #old_ident #ty_generics #into_where_clause {
+                /// returns every field wrapped in the variant of its type, moving them out instead of cloning them
+                pub fn into_enums(self) -> Vec<#target> {
+                    #into_values
+                }
+            }
         }
+    });
+
+    let result = quote! {
+        #deprecations
 
-        impl #old_ident{
-            pub fn gets_enums(&self) -> Vec<#ident> {
-                let mut result = Vec::new();
-                #(result.push(#ident::from(self.#struct_fields.clone()));)*
-                result
+        #type_enum
+
+        #runtime_impl
+
+        impl #impl_generics #old_ident #ty_generics #where_clause {
+            pub fn #method(&self) -> Vec<#target>
+            where
+                #clone_bounds
+            {
+                #values
             }
         }
 
-        #(impl From<#from_types> for #ident{
-            fn from(value: #field_types) -> Self {
-                #ident :: #from_fields (value)
+        #into_enums
+
+        #(impl #conversion_generics From<#from_types> for #target #conversion_where_clause {
+            fn from(value: #from_types) -> Self {
+                Self::#from_fields(value)
             }
         })*
 
-        #(impl TryInto<#from_types> for #ident{
-            type Error=();
+        #enum_error
 
-            fn try_into(self) -> Result<#from_types, Self::Error> {
-                if let Self::#enum_fields (val) = self{
-                    return Ok(val);
+        #(impl #conversion_generics ::std::convert::TryFrom<#target> for #try_types #conversion_where_clause {
+            type Error = #error;
+
+            fn try_from(value: #target) -> ::std::result::Result<Self, Self::Error> {
+                match value {
+                    #enum_path::#try_fields(value) => ::std::result::Result::Ok(value),
+                    #[allow(unreachable_patterns)]
+                    value => ::std::result::Result::Err(#error {
+                        expected: #try_names,
+                        actual: #actual,
+                    }),
+                }
+            }
+        })*
+
+        #(impl #ref_impl_generics ::std::convert::TryFrom<&#lifetime #target> for &#lifetime #try_types #conversion_where_clause {
+            type Error = #error;
+
+            fn try_from(value: &#lifetime #target) -> ::std::result::Result<Self, Self::Error> {
+                match value {
+                    #enum_path::#try_fields(value) => ::std::result::Result::Ok(value),
+                    #[allow(unreachable_patterns)]
+                    value => ::std::result::Result::Err(#error {
+                        expected: #try_names,
+                        actual: #actual,
+                    }),
                 }
-                else{return Err(());}
             }
         })*
     };
     //println!("{result}");
-    result.into()
+    Ok(result)
 }
 
 /**
-you can also have it derive traits by adding them to the `MutEnumDerives` attribute like this:
+you can also have it derive traits by adding them to `mut_enum_derive(...)` in the `struct_tools` attribute like this:
 
 ```rust
-# use struct_tools_derive::{StructEnumMut};
+# use struct_tools_derive::StructEnumMut;
 #[derive(StructEnumMut)]
-#[MutEnumDerive(Debug)]
+#[struct_tools(mut_enum_derive(Debug))]
 pub struct Foo{
     field1: i32,
     field2: String,
@@ -1115,130 +2275,344 @@ pub struct Foo{
 }
 ```
 
+`&'a mut T` implements `TryFrom<FooEnumMut<'a>>`, failing with a generated `FooEnumMutError`
+like the `FooEnumError` of [`StructEnum`](macro@StructEnum).
+
 TODO!
 
 */
-#[proc_macro_derive(StructEnumMut, attributes(MutEnumDerive))]
+#[proc_macro_derive(StructEnumMut, attributes(struct_tools, MutEnumDerive))]
 pub fn derive_struct_enum_mut(input: TokenStream) -> TokenStream {
-    let ast = parse_macro_input!(input as DeriveInput);
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_struct_enum_mut(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_struct_enum_mut(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
     let DeriveInput {
-        attrs, ident, data, ..
-    } = ast;
+        attrs,
+        vis,
+        ident,
+        data,
+        generics,
+    } = input;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     //println!("{attrs:?}\n");
 
-    let attr: Vec<Attribute> = attrs
-        .into_iter()
-        .filter(|attr| attr.path().is_ident("MutEnumDerive"))
-        .collect();
-    //println!("{attr:?}\n");
+    let attrs = ContainerAttrs::parse(&attrs)?;
+    let vis = attrs.item_vis(&attrs.enum_vis, &vis);
+    let derives = derive_attribute(&attrs.mut_enum_derive);
 
     let old_ident = ident.clone();
 
-    let ident = Ident::new(&(ident.to_string() + "EnumMut"), ident.span());
+    let ident = generated_name(&attrs.type_enum_mut, &ident, "EnumMut");
+    let method = attrs
+        .enum_mut_method
+        .clone()
+        .unwrap_or_else(|| format_ident!("gets_enums_mut"));
 
-    let derives = match attr.is_empty() {
-        false => Some(
-            attr.into_iter()
-                .flat_map(|attr| attr.parse_args::<proc_macro2::TokenStream>()),
-        ),
-        true => None,
-    };
-    let derives = derives.map(|iter| quote! {#[derive (#(#iter),*)]});
-
-    let fields = match data {
-        Struct(DataStruct {
-            fields: Named(FieldsNamed { ref named, .. }),
-            ..
-        }) => named,
-        _ => todo!(),
-    };
+    let fields = struct_fields(&data, &attrs, "StructEnumMut")?;
+    let deprecations = deprecations(&attrs, &fields, &["MutEnumDerive"]);
+    let fields = fields
+        .into_iter()
+        .filter(|field| !field.attrs.skip.enums)
+        .collect_vec();
 
     let field_types = fields
         .iter()
-        .map(|field| &field.ty)
+        .map(|field| field.ty())
         .unique()
         .collect::<Vec<&Type>>();
-    let struct_fields = fields.iter().flat_map(|field| &field.ident).collect_vec();
-    let enum_fields = field_types
+    let struct_members = fields.iter().map(|field| &field.member).collect_vec();
+    let enum_fields = type_variants(&field_types, &fields)?;
+    let struct_variants = fields
         .iter()
-        .cloned()
-        .map(|typ| {
-            let string = typ
-                .to_token_stream()
-                .to_string()
-                .char_indices()
-                .map(|(i, chr)| {
-                    if i == 0 {
-                        chr.to_uppercase().to_string()
-                    //} else if vec!['<','>','(',')','[',']'].contains(&chr){
-                    //    String::new()
-                    } else if !chr.is_ascii_alphabetic() && !chr.is_ascii_alphanumeric() {
-                        String::new()
-                    } else {
-                        chr.to_string()
-                    }
-                })
-                .collect::<String>();
-            let string = string.replace(' ', "");
-            let string = string.replace('(', "");
-            let string = string.replace(')', "");
-            Ident::new(&string, Span::call_site().into())
-        })
-        .collect::<Vec<Ident>>();
-    let from_fields = enum_fields.clone();
+        .map(|field| &enum_fields[field_types.iter().position(|ty| *ty == field.ty()).unwrap()])
+        .collect_vec();
+
+    let (from_fields, from_types): (Vec<_>, Vec<&&Type>) = enum_fields
+        .iter()
+        .zip(field_types.iter())
+        .filter(|(_, ty)| generics::converts(&generics, &field_types, ty).0)
+        .unzip();
+    let (try_fields, try_types): (Vec<_>, Vec<&&Type>) = enum_fields
+        .iter()
+        .zip(field_types.iter())
+        .filter(|(_, ty)| generics::converts(&generics, &field_types, ty).1)
+        .unzip();
+
+    let generics::BorrowingGenerics {
+        args: used_args,
+        lifetime,
+        generics: enum_generics,
+        return_args,
+    } = generics::borrowing_generics(&generics, &field_types);
+    let (enum_impl_generics, enum_ty_generics, enum_where_clause) = enum_generics.split_for_impl();
+
+    let accessors = variant_accessors(&enum_fields, &field_types, Some(&lifetime))?;
+    // an error of its own, so it doesn't depend on `StructEnum` being derived as well
+    let error = generated_name(&attrs.enum_mut_error, &old_ident, "EnumMutError");
+    let enum_error = match field_types.is_empty() {
+        true => None,
+        false => Some(enum_error(vis, &error)),
+    };
+    let try_names = try_fields
+        .iter()
+        .map(|field| field.to_string())
+        .collect_vec();
+    let actual = variant_name(&quote!(#ident), &enum_fields, false);
+
+    let runtime_impl = runtime_crate(&attrs).map(|runtime| {
+        let enum_type = borrowing_enum_type(
+            "TypeEnumMut",
+            &ident,
+            &generics,
+            &used_args,
+            field_types.is_empty(),
+        );
+        quote! {
+            impl #impl_generics #runtime::HasTypeEnumMut for #old_ident #ty_generics #where_clause {
+                #enum_type
+
+                fn gets_enums_mut(&mut self) -> ::std::vec::Vec<Self::TypeEnumMut<'_>> {
+                    Self::#method(self)
+                }
+            }
+        }
+    });
 
     let result = quote! {
+        #deprecations
+
         #derives
-        pub enum #ident<'a> {
-            #(#enum_fields (&'a mut #field_types)),*
+        #vis enum #ident #enum_impl_generics #enum_where_clause {
+            #(#enum_fields (&#lifetime mut #field_types)),*
         }
 
-        impl #old_ident{
-            pub fn gets_enums_mut(&mut self) -> Vec<#ident> {
-                let mut result = Vec::new();
-                #(result.push(#ident::from(&mut self.#struct_fields));)*
-                result
+        #runtime_impl
+
+        impl #enum_impl_generics #ident #enum_ty_generics #enum_where_clause {
+            #accessors
+        }
+
+        #enum_error
+
+        impl #impl_generics #old_ident #ty_generics #where_clause {
+            pub fn #method(&mut self) -> Vec<#ident #return_args> {
+                vec![#(#ident::#struct_variants(&mut self.#struct_members)),*]
             }
         }
 
-        #(impl<'a> From<&'a mut #field_types> for #ident<'a>{
-            fn from(value: &'a mut #field_types) -> Self {
+        #(impl #enum_impl_generics From<&#lifetime mut #from_types> for #ident #enum_ty_generics #enum_where_clause {
+            fn from(value: &#lifetime mut #from_types) -> Self {
                 #ident :: #from_fields (value)
             }
         })*
 
-        #(impl<'a> TryInto<&'a mut #field_types> for #ident<'a>{
-            type Error=();
-
-            fn try_into(self) -> Result<&'a mut #field_types, Self::Error> {
-                if let Self::#enum_fields (val) = self{
-                    return Ok(val);
-                } else {
-                return Err(());
+        #(impl #enum_impl_generics ::std::convert::TryFrom<#ident #enum_ty_generics> for &#lifetime mut #try_types #enum_where_clause {
+            type Error = #error;
+
+            fn try_from(value: #ident #enum_ty_generics) -> ::std::result::Result<Self, Self::Error> {
+                match value {
+                    #ident::#try_fields(value) => ::std::result::Result::Ok(value),
+                    #[allow(unreachable_patterns)]
+                    value => ::std::result::Result::Err(#error {
+                        expected: #try_names,
+                        actual: #actual,
+                    }),
+                }
             }
+        })*
+
+        #(impl #enum_impl_generics ::std::convert::TryFrom<&#lifetime mut #ident #enum_ty_generics> for &#lifetime mut #try_types #enum_where_clause {
+            type Error = #error;
+
+            fn try_from(value: &#lifetime mut #ident #enum_ty_generics) -> ::std::result::Result<Self, Self::Error> {
+                match value {
+                    #ident::#try_fields(value) => ::std::result::Result::Ok(*value),
+                    #[allow(unreachable_patterns)]
+                    value => ::std::result::Result::Err(#error {
+                        expected: #try_names,
+                        actual: #actual,
+                    }),
+                }
             }
         })*
 
-        #(impl<'a> TryInto<&'a mut #field_types> for &'a mut #ident<'a>{
-            type Error=();
+    };
+    //println!("{result}");
+    Ok(result)
+}
+
+/**
+Will create an Enum which is capable of borrowing all possible contents of the struct, without
+cloning them
+
+Its Variants are named like the ones of [`StructEnum`](macro@StructEnum), holding `&'a` references
+instead of the values
 
-            fn try_into(self) -> Result<&'a mut #field_types, Self::Error> {
-                match self {
-                    #ident ::#enum_fields(val) => Ok(*val),
-                    _ => Err(()),
+```rust
+use struct_tools_derive::StructEnumRef;
+
+#[derive(StructEnumRef)]
+#[struct_tools(ref_enum_derive(Debug, PartialEq))]
+pub struct Foo{
+    field1: i32,
+    field2: String,
+    //{...}
+}
+
+let foo = Foo{ field1: 1, field2: String::from("two") };
+let two = String::from("two");
+assert_eq!(foo.gets_enums_ref(), vec![FooEnumRef::I32(&1), FooEnumRef::String(&two)]);
+```
+
+Every `&'a T` converts into it with `From`, and `cloned` clones the value into any enum with a `From`
+impl for every Type, like the `FooEnum` of `StructEnum`:
+
+```rust
+use struct_tools_derive::{StructEnum, StructEnumRef};
+
+#[derive(StructEnum, StructEnumRef)]
+#[struct_tools(enum_derive(Debug, PartialEq), ref_enum_derive(Debug, PartialEq))]
+pub struct Foo{
+    field1: i32,
+    field2: String,
+}
+
+assert_eq!(FooEnumRef::from(&1), FooEnumRef::I32(&1));
+
+let foo = Foo{ field1: 1, field2: String::from("two") };
+let owned: FooEnum = foo.gets_enums_ref()[1].cloned();
+assert_eq!(owned, FooEnum::String(String::from("two")));
+```
+*/
+#[proc_macro_derive(StructEnumRef, attributes(struct_tools))]
+pub fn derive_struct_enum_ref(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_struct_enum_ref(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_struct_enum_ref(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let DeriveInput {
+        attrs,
+        vis,
+        ident,
+        data,
+        generics,
+    } = input;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let attrs = ContainerAttrs::parse(&attrs)?;
+    let vis = attrs.item_vis(&attrs.enum_vis, &vis);
+    let derives = derive_attribute(&attrs.ref_enum_derive);
+
+    let old_ident = ident.clone();
+
+    let ident = generated_name(&attrs.type_enum_ref, &ident, "EnumRef");
+    let method = attrs
+        .enum_ref_method
+        .clone()
+        .unwrap_or_else(|| format_ident!("gets_enums_ref"));
+
+    let item_fields =
+        data_fields(&data, &attrs, "StructEnumRef")?.without(|field| field.attrs.skip.enums);
+    let fields = item_fields.all();
+
+    let field_types = fields
+        .iter()
+        .map(|field| field.ty())
+        .unique()
+        .collect::<Vec<&Type>>();
+    let enum_fields = type_variants(&field_types, fields.iter().copied())?;
+    let values = item_fields.collect_values(|_, field, value| {
+        let variant = &enum_fields[field_types.iter().position(|ty| *ty == field.ty()).unwrap()];
+        quote!(#ident::#variant(#value))
+    });
+
+    let generics::BorrowingGenerics {
+        args: used_args,
+        lifetime,
+        generics: enum_generics,
+        return_args,
+    } = generics::borrowing_generics(&generics, &field_types);
+    let (enum_impl_generics, enum_ty_generics, enum_where_clause) = enum_generics.split_for_impl();
+
+    let (from_fields, from_types): (Vec<_>, Vec<&&Type>) = enum_fields
+        .iter()
+        .zip(field_types.iter())
+        .filter(|(_, ty)| generics::converts(&generics, &field_types, ty).0)
+        .unzip();
+    let owned_bounds = field_types
+        .iter()
+        .map(|ty| -> WherePredicate { parse_quote!(E: ::std::convert::From<#ty>) })
+        .chain(field_types.iter().map(|ty| clone_bound(ty)));
+
+    let runtime_impl = runtime_crate(&attrs).map(|runtime| {
+        let enum_type = borrowing_enum_type(
+            "TypeEnumRef",
+            &ident,
+            &generics,
+            &used_args,
+            field_types.is_empty(),
+        );
+        quote! {
+            impl #impl_generics #runtime::HasTypeEnumRef for #old_ident #ty_generics #where_clause {
+                #enum_type
+
+                fn gets_enums_ref(&self) -> ::std::vec::Vec<Self::TypeEnumRef<'_>> {
+                    Self::#method(self)
                 }
             }
-        })*
+        }
+    });
+
+    let result = quote! {
+        #derives
+        #vis enum #ident #enum_impl_generics #enum_where_clause {
+            #(#enum_fields (&#lifetime #field_types)),*
+        }
+
+        #runtime_impl
+
+        impl #impl_generics #old_ident #ty_generics #where_clause {
+            pub fn #method(&self) -> Vec<#ident #return_args> {
+                #values
+            }
+        }
+
+        impl #enum_impl_generics #ident #enum_ty_generics #enum_where_clause {
+            /// clones the value into an owned enum with a variant for its type, e.g. the one of `StructEnum`
+            pub fn cloned<E>(&self) -> E
+            where
+                #(#owned_bounds,)*
+            {
+                match *self {
+                    #(Self::#enum_fields(value) => <E as ::std::convert::From<#field_types>>::from(::std::clone::Clone::clone(value)),)*
+                }
+            }
+        }
 
+        #(impl #enum_impl_generics From<&#lifetime #from_types> for #ident #enum_ty_generics #enum_where_clause {
+            fn from(value: &#lifetime #from_types) -> Self {
+                #ident :: #from_fields (value)
+            }
+        })*
     };
     //println!("{result}");
-    result.into()
+    Ok(result)
 }
 
 /**
 Will create an Enum which is capable of containing all possible contents of the struct
 
+Derived for an enum, it contains the contents of every variant, and the values are taken from the
+active variant.
+
 # Example
 
 If you have a struct
@@ -1266,7 +2640,7 @@ pub struct Foo{
 
 This Grants you access to an automatically generated Enum with the name `{structname}FieldEnum`.
 
-its Variants are named by CapitalCamelCase-ing the fields of the struct
+its Variants are named by CapitalCamelCase-ing the fields of the struct (`Field0`, `Field1`, ... for tuple structs)
 
 ```rust
 pub enum FooFieldEnum{
@@ -1276,236 +2650,1049 @@ pub enum FooFieldEnum{
 }
 ```
 
-you can also have it derive traits by adding them to the `EnumDerives` attribute like this:
+Every variant gets the methods `is_*`, `as_*`, `as_*_mut`, `into_*` and `expect_*`, named after it
+in snake_case, which `StructFieldEnumMut` generates for its variants as well:
+
+```rust
+# use struct_tools_derive::StructFieldEnum;
+#[derive(StructFieldEnum)]
+pub struct Foo{
+    field1: i32,
+    field2: String,
+}
+
+let value = FooFieldEnum::Field1(4);
+assert!(value.is_field1());
+assert_eq!(value.as_field2(), None);
+assert_eq!(value.expect_field1(), 4);
+```
+
+you can also have it derive traits by adding them to `enum_derive(...)` in the `struct_tools` attribute like this:
 
 ```rust
 # use struct_tools_derive::{StructFieldEnum, StructIterTools};
 #[derive(StructFieldEnum)]
-#[EnumDerive(Debug)]
+#[struct_tools(enum_derive(Debug))]
 pub struct Foo{
     field1: i32,
     field2: String,
     //{...}
 }
 ```
+
+The fields of a struct can be set with `set_field`, or with `set_field_by_name` by a name only known
+at runtime, which converts the value with `TryInto` and fails with a `{structname}FieldError`:
+
+```rust
+use struct_tools_derive::{StructEnum, StructFieldEnum};
+
+#[derive(Default, StructEnum, StructFieldEnum)]
+pub struct Foo{
+    field1: i32,
+    field2: String,
+}
+
+let mut foo = Foo::default();
+foo.set_field(FooFieldEnum::Field1(1));
+assert_eq!(foo.set_field_by_name("field2", FooEnum::String(String::from("two"))), Ok(()));
+assert_eq!(foo.set_field_by_name("field2", FooEnum::I32(2)), Err(FooFieldError::WrongType("field2")));
+assert_eq!(foo.field1, 1);
+```
+
+`diff` returns a `{structname}FieldChange` for every field two values of a struct differ in,
+leaving out the fields marked `#[struct_tools(diff(skip))]`:
+
+```rust
+use struct_tools_derive::StructFieldEnum;
+
+#[derive(StructFieldEnum)]
+#[struct_tools(enum_derive(Debug, PartialEq))]
+pub struct Foo{
+    field1: i32,
+    #[struct_tools(diff(skip))]
+    updated_at: u64,
+}
+
+let changes = Foo{ field1: 1, updated_at: 1 }.diff(&Foo{ field1: 2, updated_at: 2 });
+assert_eq!(changes.len(), 1);
+assert_eq!(changes[0].new, FooFieldEnum::Field1(2));
+```
 */
-#[proc_macro_derive(StructFieldEnum, attributes(EnumDerive, StructFields, StructValues))]
+#[proc_macro_derive(
+    StructFieldEnum,
+    attributes(struct_tools, EnumDerive, StructFields, StructValues)
+)]
 pub fn derive_struct_field_enum(input: TokenStream) -> TokenStream {
-    let ast = parse_macro_input!(input as DeriveInput);
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_struct_field_enum(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_struct_field_enum(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
     let DeriveInput {
-        attrs, ident, data, ..
-    } = ast;
-
-    let new_ident = Ident::new(&(ident.to_string() + "FieldEnum"), ident.span());
-
-    let derives = match attrs.is_empty() {
-        false => Some(
-            attrs
-                .into_iter()
-                .filter(|attr| attr.path().is_ident("EnumDerive"))
-                .flat_map(|attr| attr.parse_args::<proc_macro2::TokenStream>()),
-        ),
-        true => None,
-    };
-    let derives = derives.map(|iter| quote! {#[derive (#(#iter),*)]});
-
-    let fields = match data {
-        Struct(DataStruct {
-            fields: Named(FieldsNamed { ref named, .. }),
-            ..
-        }) => named,
-        _ => todo!(),
-    };
-    let fields_vec = fields
-        .iter()
-        .filter_map(|field| field.ident.as_ref())
-        .collect_vec();
+        attrs,
+        vis,
+        ident,
+        data,
+        generics,
+    } = input;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let attrs = ContainerAttrs::parse(&attrs)?;
+    let vis = attrs.item_vis(&attrs.field_enum_vis, &vis);
+
+    let new_ident = generated_name(&attrs.field_enum, &ident, "FieldEnum");
+    let method = attrs
+        .field_enum_method
+        .clone()
+        .unwrap_or_else(|| format_ident!("get_fields_enums"));
+    let derives = derive_attribute(&attrs.enum_derive);
+
+    let item_fields = data_fields(&data, &attrs, "StructFieldEnum")?;
+    let deprecations = deprecations(&attrs, item_fields.all(), &["EnumDerive"]);
+    let item_fields = item_fields.without(|field| field.attrs.skip.enums);
+    let fields = item_fields.all();
 
-    let fields_str: std::vec::Vec<std::string::String> = fields
+    let field_types = fields
         .iter()
-        .filter_map(|field| field.ident.as_ref().map(|id| format!("{id}")))
-        .collect::<Vec<String>>();
+        .map(|field| field.ty())
+        .collect::<Vec<&Type>>();
 
-    let field_types = fields.iter().map(|field| &field.ty).collect::<Vec<&Type>>();
-
-    let variants_str: Vec<String> = fields_str
+    let variants_str: Vec<String> = fields.iter().map(|field| field.variant_name()).collect();
+    let variants = fields
         .iter()
-        .cloned()
-        .map(|field| {
-            field
-                .chars()
-                .enumerate()
-                .map(|(i, chr)| match i {
-                    0 => chr.to_uppercase().to_string(),
-                    _ => chr.to_string(),
-                })
-                .collect::<String>()
+        .map(|field| field.variant_ident())
+        .collect::<syn::Result<Vec<_>>>()?;
+    let values = item_fields.collect_values(|i, _, value| {
+        let variant = &variants[i];
+        quote!(#new_ident::#variant(::std::clone::Clone::clone(#value)))
+    });
+    // only with `into`, see `derive_struct_iter_tools`
+    let into_values = attrs.into.then(|| {
+        item_fields.with_owned_fields(|fields| {
+            let values = fields.iter().map(|(i, _, value)| {
+                let variant = &variants[*i];
+                quote!(#new_ident::#variant(#value))
+            });
+            quote!(::std::vec![#(#values),*])
         })
-        .map(|mut field| {
-            let i = field.find('_');
-            let mut field = match i {
-                Some(i) => {
-                    field.remove(i);
-                    let mut field = field.chars().map(|chr| chr.to_string()).collect_vec();
-                    if field.get(i).is_some() {
-                        field[i] = field[i].to_uppercase();
+    });
+    let enum_generics = generics::filter_generics(&generics, field_types.iter().copied());
+    let (enum_impl_generics, enum_ty_generics, enum_where_clause) = enum_generics.split_for_impl();
+    let accessors = variant_accessors(&variants, &field_types, None)?;
+    // the field of an enum can't be set without knowing which variant is the active one
+    let set_fields = match &item_fields {
+        DataFields::Struct(fields) => {
+            check_unique_names(fields)?;
+            let error = generated_name(&attrs.field_error, &ident, "FieldError");
+            let members = fields.iter().map(|field| &field.member).collect_vec();
+            let patterns = fields.iter().map(|field| field.name_pattern());
+            let names = fields.iter().map(|field| &field.external_name);
+            let unique_types = field_types.iter().unique();
+            Some(quote! {
+                /// The error of setting a field by its name
+                #[derive(Debug, Clone, PartialEq, Eq)]
+                #vis enum #error {
+                    /// There is no field with the name
+                    UnknownField(::std::string::String),
+                    /// The value can't be converted into the type of the field with the name
+                    WrongType(&'static str),
+                }
+                impl ::std::fmt::Display for #error {
+                    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                        match self {
+                            Self::UnknownField(name) => write!(f, "there is no field named `{}`", name),
+                            Self::WrongType(name) => write!(f, "the value doesn't have the type of the field `{}`", name),
+                        }
+                    }
+                }
+                impl ::std::error::Error for #error {}
+
+                impl #impl_generics #ident #ty_generics #where_clause {
+                    /// replaces the value of the field `value` is a variant of
+                    pub fn set_field(&mut self, value: #new_ident #enum_ty_generics) {
+                        match value {
+                            #(#new_ident::#variants(value) => self.#members = value,)*
+                        }
+                    }
+
+                    /// replaces the value of the field named `name`, or one of its aliases, with `value` converted into the type of the field
+                    pub fn set_field_by_name<E>(&mut self, name: &str, value: E) -> ::std::result::Result<(), #error>
+                    where
+                        #(E: ::std::convert::TryInto<#unique_types>),*
+                    {
+                        match name.as_bytes() {
+                            #(#patterns => {
+                                self.#members = <E as ::std::convert::TryInto<#field_types>>::try_into(value)
+                                    .map_err(|_| #error::WrongType(#names))?;
+                                Ok(())
+                            })*
+                            _ => Err(#error::UnknownField(::std::string::String::from(name))),
+                        }
                     }
-                    field.join("")
                 }
-                None => field,
+            })
+        }
+        DataFields::Enum(_) => None,
+    };
+    // two values of an enum might not even be the same variant
+    let diff = match &item_fields {
+        DataFields::Struct(fields) => {
+            let change = generated_name(&attrs.field_change, &ident, "FieldChange");
+            let (compared, compared_variants): (Vec<_>, Vec<_>) = fields
+                .iter()
+                .zip(&variants)
+                .filter(|(field, _)| !field.attrs.diff_skip)
+                .unzip();
+            let members = compared.iter().map(|field| &field.member).collect_vec();
+            let names = compared.iter().map(|field| field.variant_name());
+            let bounds = compared
+                .iter()
+                .map(|field| field.ty())
+                .unique()
+                .flat_map(|ty| [clone_bound(ty), partial_eq_bound(ty)]);
+            let changes = match compared.is_empty() {
+                true => quote!(::std::vec::Vec::new()),
+                false => quote! {
+                    let mut changes = ::std::vec::Vec::new();
+                    #(if ::std::cmp::PartialEq::ne(&self.#members, &other.#members) {
+                        changes.push(#change {
+                            field: #names,
+                            old: #new_ident::#compared_variants(::std::clone::Clone::clone(&self.#members)),
+                            new: #new_ident::#compared_variants(::std::clone::Clone::clone(&other.#members)),
+                        });
+                    })*
+                    changes
+                },
             };
-            let i = field.find('_');
-            match i {
-                Some(i) => {
-                    field.remove(i);
-                    let mut field = field.chars().map(|chr| chr.to_string()).collect_vec();
-                    if field.get(i).is_some() {
-                        field[i] = field[i].to_uppercase();
+            Some(quote! {
+                /// A field that differs between two values, as returned by `diff`
+                #derives
+                #vis struct #change #enum_impl_generics #enum_where_clause {
+                    /// The name of the variant of the field, as returned by `get_variants`
+                    pub field: &'static str,
+                    /// The value of the field in `self`
+                    pub old: #new_ident #enum_ty_generics,
+                    /// The value of the field in `other`
+                    pub new: #new_ident #enum_ty_generics,
+                }
+
+                impl #impl_generics #ident #ty_generics #where_clause {
+                    /// returns every field whose value in `other` differs from the one in `self`
+                    pub fn diff(&self, other: &Self) -> ::std::vec::Vec<#change #enum_ty_generics>
+                    where
+                        #(#bounds),*
+                    {
+                        #changes
                     }
-                    field.join("")
                 }
-                None => field,
+            })
+        }
+        DataFields::Enum(_) => None,
+    };
+    let clone_bounds = field_types.iter().map(|ty| clone_bound(ty));
+    let into_fields_enums = into_values.map(|into_values| {
+        quote! {
+            /// returns every field wrapped in its variant, moving them out instead of cloning them
+            pub fn into_fields_enums(self) -> Vec< #new_ident #enum_ty_generics > {
+                #into_values
             }
-        })
-        .collect_vec();
-    let variants = variants_str
-        .iter()
-        .map(|variant| {
-            let variant = Ident::new(variant, Span::call_site().into());
-            quote! {#variant}
-        })
-        .collect_vec();
+        }
+    });
     let get_fields_enums = quote! {
-        impl #ident {
-            pub fn get_fields_enums(&self) -> Vec< #new_ident > {
-                vec![#(#new_ident :: #variants (self. #fields_vec .clone())),*]
+        impl #impl_generics #ident #ty_generics #where_clause {
+            pub fn #method(&self) -> Vec< #new_ident #enum_ty_generics >
+            where
+                #(#clone_bounds),*
+            {
+                #values
             }
+
+            #into_fields_enums
         }
     };
+    let runtime_impl = runtime_crate(&attrs).map(|runtime| {
+        let clone_generics =
+            generics::with_predicates(&generics, field_types.iter().map(|ty| clone_bound(ty)));
+        let where_clause = &clone_generics.where_clause;
+        quote! {
+            impl #impl_generics #runtime::HasFieldEnum for #ident #ty_generics #where_clause {
+                type FieldEnum = #new_ident #enum_ty_generics;
+
+                fn get_fields_enums(&self) -> ::std::vec::Vec<Self::FieldEnum> {
+                    Self::#method(self)
+                }
+
+                fn get_variants() -> ::std::vec::Vec<&'static str> {
+                    <#new_ident #enum_ty_generics>::get_variants()
+                }
+            }
+        }
+    });
     let result = quote! {
+        #deprecations
+
         #derives
-        pub enum #new_ident{
+        #vis enum #new_ident #enum_impl_generics #enum_where_clause {
             #(#variants (#field_types)),*
         }
         #get_fields_enums
-        impl #new_ident{
+        #set_fields
+        #diff
+        impl #enum_impl_generics #new_ident #enum_ty_generics #enum_where_clause {
             pub fn get_variants() -> Vec<&'static str> {
                 vec![#( #variants_str ),*]
             }
+
+            #accessors
         }
+        #runtime_impl
     };
     //println!("{result}");
-    result.into()
+    Ok(result)
 }
 
 #[proc_macro_derive(
     StructFieldEnumMut,
-    attributes(MutEnumDerive, StructFields, StructValues)
+    attributes(struct_tools, MutEnumDerive, StructFields, StructValues)
 )]
 pub fn derive_struct_field_enum_mut(input: TokenStream) -> TokenStream {
-    let ast = parse_macro_input!(input as DeriveInput);
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_struct_field_enum_mut(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_struct_field_enum_mut(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
     let DeriveInput {
-        attrs, ident, data, ..
-    } = ast;
-
-    let new_ident = Ident::new(&(ident.to_string() + "FieldEnumMut"), ident.span());
-
-    let derives = match attrs.is_empty() {
-        false => Some(
-            attrs
-                .into_iter()
-                .filter(|attr| attr.path().is_ident("MutEnumDerive"))
-                .flat_map(|attr| attr.parse_args::<proc_macro2::TokenStream>()),
-        ),
-        true => None,
-    };
-    let derives = derives.map(|iter| quote! {#[derive (#(#iter),*)]});
-
-    let fields = match data {
-        Struct(DataStruct {
-            fields: Named(FieldsNamed { ref named, .. }),
-            ..
-        }) => named,
-        _ => todo!(),
-    };
-    let fields_vec = fields
-        .iter()
-        .filter_map(|field| field.ident.as_ref())
+        attrs,
+        vis,
+        ident,
+        data,
+        generics,
+    } = input;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let attrs = ContainerAttrs::parse(&attrs)?;
+    let vis = attrs.item_vis(&attrs.field_enum_vis, &vis);
+
+    let new_ident = generated_name(&attrs.field_enum_mut, &ident, "FieldEnumMut");
+    let method = attrs
+        .field_enum_mut_method
+        .clone()
+        .unwrap_or_else(|| format_ident!("get_fields_enums_mut"));
+    let derives = derive_attribute(&attrs.mut_enum_derive);
+
+    let fields = struct_fields(&data, &attrs, "StructFieldEnumMut")?;
+    let deprecations = deprecations(&attrs, &fields, &["MutEnumDerive"]);
+    let fields = fields
+        .into_iter()
+        .filter(|field| !field.attrs.skip.enums)
         .collect_vec();
+    let fields_vec = fields.iter().map(|field| &field.member).collect_vec();
 
-    let fields_str: std::vec::Vec<std::string::String> = fields
+    let field_types = fields
         .iter()
-        .filter_map(|field| field.ident.as_ref().map(|id| format!("{id}")))
-        .collect::<Vec<String>>();
-
-    let field_types = fields.iter().map(|field| &field.ty).collect::<Vec<&Type>>();
+        .map(|field| field.ty())
+        .collect::<Vec<&Type>>();
 
-    let variants_str: Vec<String> = fields_str
+    let variants_str: Vec<String> = fields.iter().map(|field| field.variant_name()).collect();
+    let variants = fields
         .iter()
-        .cloned()
-        .map(|field| {
-            field
-                .chars()
-                .enumerate()
-                .map(|(i, chr)| match i {
-                    0 => chr.to_uppercase().to_string(),
-                    _ => chr.to_string(),
-                })
-                .collect::<String>()
-        })
-        .map(|mut field| {
-            let i = field.find('_');
-            let mut field = match i {
-                Some(i) => {
-                    field.remove(i);
-                    let mut field = field.chars().map(|chr| chr.to_string()).collect_vec();
-                    if field.get(i).is_some() {
-                        field[i] = field[i].to_uppercase();
-                    }
-                    field.join("")
+        .map(|field| field.variant_ident())
+        .collect::<syn::Result<Vec<_>>>()?;
+    let generics::BorrowingGenerics {
+        args: used_args,
+        lifetime,
+        generics: enum_generics,
+        return_args,
+    } = generics::borrowing_generics(&generics, &field_types);
+    let (enum_impl_generics, enum_ty_generics, enum_where_clause) = enum_generics.split_for_impl();
+    let accessors = variant_accessors(&variants, &field_types, Some(&lifetime))?;
+    let get_fields_enums_mut = quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            pub fn #method(&mut self) -> Vec< #new_ident #return_args > {
+                vec![#(#new_ident :: #variants (&mut self. #fields_vec)),*]
+            }
+        }
+    };
+    let runtime_impl = runtime_crate(&attrs).map(|runtime| {
+        let enum_type = borrowing_enum_type(
+            "FieldEnumMut",
+            &new_ident,
+            &generics,
+            &used_args,
+            field_types.is_empty(),
+        );
+        quote! {
+            impl #impl_generics #runtime::HasFieldEnumMut for #ident #ty_generics #where_clause {
+                #enum_type
+
+                fn get_fields_enums_mut(&mut self) -> ::std::vec::Vec<Self::FieldEnumMut<'_>> {
+                    Self::#method(self)
                 }
-                None => field,
-            };
-            let i = field.find('_');
-            match i {
-                Some(i) => {
-                    field.remove(i);
-                    let mut field = field.chars().map(|chr| chr.to_string()).collect_vec();
-                    if field.get(i).is_some() {
-                        field[i] = field[i].to_uppercase();
-                    }
-                    field.join("")
+
+                fn get_variants() -> ::std::vec::Vec<&'static str> {
+                    <#new_ident #return_args>::get_variants()
                 }
-                None => field,
             }
-        })
-        .collect_vec();
-    let variants = variants_str
+        }
+    });
+    let result = quote! {
+        #deprecations
+
+        #derives
+        #vis enum #new_ident #enum_impl_generics #enum_where_clause {
+            #(#variants (&#lifetime mut #field_types)),*
+        }
+        #get_fields_enums_mut
+        impl #enum_impl_generics #new_ident #enum_ty_generics #enum_where_clause {
+            pub fn get_variants() -> Vec<&'static str> {
+                vec![#( #variants_str ),*]
+            }
+
+            #accessors
+        }
+        #runtime_impl
+    };
+    //println!("{result}");
+    Ok(result)
+}
+
+/**
+Will create an Enum which is capable of borrowing all possible contents of the struct, without
+cloning them
+
+Its Variants are named like the ones of [`StructFieldEnum`](macro@StructFieldEnum), holding `&'a`
+references instead of the values. Besides `get_fields_enums_ref` returning them in a `Vec`, they
+can be iterated over from both ends with `iter_fields`
+
+```rust
+use struct_tools_derive::StructFieldEnumRef;
+
+#[derive(StructFieldEnumRef)]
+#[struct_tools(ref_enum_derive(Debug, PartialEq))]
+pub struct Foo{
+    field1: i32,
+    field2: String,
+    //{...}
+}
+
+let foo = Foo{ field1: 1, field2: String::from("two") };
+let mut fields = foo.iter_fields();
+assert_eq!(fields.len(), 2);
+assert_eq!(fields.next_back(), Some(FooFieldEnumRef::Field2(&String::from("two"))));
+assert_eq!(fields.next(), Some(FooFieldEnumRef::Field1(&1)));
+
+// the fields can also be looked up by a name or a position only known at runtime
+assert_eq!(foo.get_field("field1"), Some(FooFieldEnumRef::Field1(&1)));
+assert_eq!(foo.get_field_by_index(1), Some(FooFieldEnumRef::Field2(&String::from("two"))));
+```
+*/
+#[proc_macro_derive(StructFieldEnumRef, attributes(struct_tools))]
+pub fn derive_struct_field_enum_ref(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_struct_field_enum_ref(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_struct_field_enum_ref(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let DeriveInput {
+        attrs,
+        vis,
+        ident,
+        data,
+        generics,
+    } = input;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let attrs = ContainerAttrs::parse(&attrs)?;
+    let vis = attrs.item_vis(&attrs.field_enum_vis, &vis);
+
+    let new_ident = generated_name(&attrs.field_enum_ref, &ident, "FieldEnumRef");
+    let method = attrs
+        .field_enum_ref_method
+        .clone()
+        .unwrap_or_else(|| format_ident!("get_fields_enums_ref"));
+    let derives = derive_attribute(&attrs.ref_enum_derive);
+
+    let item_fields =
+        data_fields(&data, &attrs, "StructFieldEnumRef")?.without(|field| field.attrs.skip.enums);
+    let fields = item_fields.all();
+    match &item_fields {
+        DataFields::Struct(fields) => check_unique_names(fields)?,
+        DataFields::Enum(variants) => {
+            for (_, fields) in variants {
+                check_unique_names(fields)?;
+            }
+        }
+    }
+
+    let field_types = fields
         .iter()
-        .map(|variant| {
-            let variant = Ident::new(variant, Span::call_site().into());
-            quote! {#variant}
-        })
-        .collect_vec();
-    let get_fields_enums_mut = quote! {
-        impl #ident {
-            pub fn get_fields_enums_mut(&mut self) -> Vec< #new_ident > {
-                vec![#(#new_ident :: #variants (&mut self. #fields_vec)),*]
+        .map(|field| field.ty())
+        .collect::<Vec<&Type>>();
+
+    let variants_str: Vec<String> = fields.iter().map(|field| field.variant_name()).collect();
+    let variants = fields
+        .iter()
+        .map(|field| field.variant_ident())
+        .collect::<syn::Result<Vec<_>>>()?;
+    let values = item_fields.collect_values(|i, _, value| {
+        let variant = &variants[i];
+        quote!(#new_ident::#variant(#value))
+    });
+    let generics::BorrowingGenerics {
+        args: used_args,
+        lifetime,
+        generics: enum_generics,
+        return_args,
+    } = generics::borrowing_generics(&generics, &field_types);
+    let (enum_impl_generics, enum_ty_generics, enum_where_clause) = enum_generics.split_for_impl();
+    // a struct always has the same fields, so they fit into an array instead of a `Vec`
+    let iter_fields = match &item_fields {
+        DataFields::Struct(fields) => {
+            let members = fields.iter().map(|field| &field.member);
+            let count = fields.len();
+            quote! {
+                let fields: [#new_ident #return_args; #count] =
+                    [#(#new_ident::#variants(&self.#members)),*];
+                ::std::iter::IntoIterator::into_iter(fields)
             }
         }
+        // `#values` of an enum without variants is a `match` that doesn't tell the type
+        DataFields::Enum(variants) if variants.is_empty() => quote! {
+            ::std::iter::IntoIterator::into_iter(::std::vec::Vec::<#new_ident #return_args>::new())
+        },
+        DataFields::Enum(_) => quote!(::std::iter::IntoIterator::into_iter(#values)),
     };
+    let get_field = item_fields.with_fields(|fields| {
+        let patterns = fields.iter().map(|(_, field, _)| field.name_pattern());
+        let values = fields.iter().map(|(i, _, value)| {
+            let variant = &variants[*i];
+            quote!(::std::option::Option::Some(#new_ident::#variant(#value)))
+        });
+        quote! {
+            match name.as_bytes() {
+                #(#patterns => #values,)*
+                _ => ::std::option::Option::None,
+            }
+        }
+    });
+    let get_field_by_index = item_fields.with_fields(|fields| {
+        let indices = 0..fields.len();
+        let values = fields.iter().map(|(i, _, value)| {
+            let variant = &variants[*i];
+            quote!(::std::option::Option::Some(#new_ident::#variant(#value)))
+        });
+        quote! {
+            match index {
+                #(#indices => #values,)*
+                _ => ::std::option::Option::None,
+            }
+        }
+    });
+    let get_fields_enums_ref = quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            pub fn #method(&self) -> Vec< #new_ident #return_args > {
+                #values
+            }
+
+            /// returns an iterator over a reference to every field wrapped in its variant
+            pub fn iter_fields(
+                &self,
+            ) -> impl ::std::iter::ExactSizeIterator<Item = #new_ident #return_args>
+                   + ::std::iter::DoubleEndedIterator {
+                #iter_fields
+            }
+
+            /// returns a reference to the field named `name`, or one of its aliases, wrapped in its variant
+            pub fn get_field(&self, name: &str) -> ::std::option::Option<#new_ident #return_args> {
+                #get_field
+            }
+
+            /// returns a reference to the field at `index` in [`Self::iter_fields`] wrapped in its variant
+            pub fn get_field_by_index(&self, index: usize) -> ::std::option::Option<#new_ident #return_args> {
+                #get_field_by_index
+            }
+        }
+    };
+    let runtime_impl = runtime_crate(&attrs).map(|runtime| {
+        let enum_type = borrowing_enum_type(
+            "FieldEnumRef",
+            &new_ident,
+            &generics,
+            &used_args,
+            field_types.is_empty(),
+        );
+        quote! {
+            impl #impl_generics #runtime::HasFieldEnumRef for #ident #ty_generics #where_clause {
+                #enum_type
+
+                fn get_fields_enums_ref(&self) -> ::std::vec::Vec<Self::FieldEnumRef<'_>> {
+                    Self::#method(self)
+                }
+
+                fn get_variants() -> ::std::vec::Vec<&'static str> {
+                    <#new_ident #return_args>::get_variants()
+                }
+            }
+        }
+    });
     let result = quote! {
         #derives
-        pub enum #new_ident<'a>{
-            #(#variants (&'a mut #field_types)),*
+        #vis enum #new_ident #enum_impl_generics #enum_where_clause {
+            #(#variants (&#lifetime #field_types)),*
         }
-        #get_fields_enums_mut
-        impl<'a> #new_ident<'a>{
+        #get_fields_enums_ref
+        impl #enum_impl_generics #new_ident #enum_ty_generics #enum_where_clause {
             pub fn get_variants() -> Vec<&'static str> {
                 vec![#( #variants_str ),*]
             }
         }
+        #runtime_impl
     };
     //println!("{result}");
-    result.into()
+    Ok(result)
+}
+
+/**
+Will create a struct holding every field as an `Option`, for updating only some of them.
+
+If you have a struct
+
+```rust
+pub struct Foo{
+    field1: i32,
+    field2: String,
+    //{...}
+}
+```
+
+you can just add the derive to it, together with `StructFieldEnum`
+
+```rust
+use struct_tools_derive::{StructFieldEnum, StructPatch};
+
+#[derive(StructFieldEnum, StructPatch)]
+pub struct Foo{
+    field1: i32,
+    field2: String,
+    //{...}
+}
+```
+
+This Grants you access to an automatically generated struct with the name `{structname}Patch`.
+
+```rust
+# pub struct Foo{
+#     field1: i32,
+#     field2: String,
+# }
+# pub enum FooFieldEnum{
+#     Field1(i32),
+#     Field2(String),
+# }
+pub struct FooPatch{
+    pub field1: Option<i32>,
+    pub field2: Option<String>,
+    //{...}
+}
+impl Default for FooPatch{
+    fn default() -> Self {
+        /*{...}*/
+#         todo!()
+    }
+}
+impl FooPatch {
+    pub fn is_empty(&self) -> bool {
+        //{...}
+#         todo!()
+    }
+    pub fn merge(self, other: Self) -> Self {
+        //{...}
+#         todo!()
+    }
+}
+impl FromIterator<FooFieldEnum> for FooPatch {
+    //{...}
+#     fn from_iter<I: IntoIterator<Item = FooFieldEnum>>(iter: I) -> Self {
+#         todo!()
+#     }
+}
+impl Foo {
+    pub fn apply_patch(&mut self, patch: FooPatch) {
+        //{...}
+    }
+}
+```
+
+`merge` prefers the fields set in `other`, so patches can be layered from the most general to the
+most specific one. The fields skipped by the enums are left out of the patch as well.
+
+```rust
+use struct_tools_derive::{StructFieldEnum, StructPatch};
+
+#[derive(Default, StructFieldEnum, StructPatch)]
+#[struct_tools(patch_derive(Debug))]
+pub struct Foo{
+    field1: i32,
+    field2: String,
+}
+
+let defaults = FooPatch{ field1: Some(1), field2: Some(String::from("one")) };
+let changes: FooPatch = vec![FooFieldEnum::Field2(String::from("two"))].into_iter().collect();
+
+let mut foo = Foo::default();
+foo.apply_patch(defaults.merge(changes));
+assert_eq!((foo.field1, foo.field2.as_str()), (1, "two"));
+assert!(FooPatch::default().is_empty());
+```
+*/
+#[proc_macro_derive(StructPatch, attributes(struct_tools))]
+pub fn derive_struct_patch(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_struct_patch(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_struct_patch(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let DeriveInput {
+        attrs,
+        vis,
+        ident,
+        data,
+        generics,
+    } = input;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let attrs = ContainerAttrs::parse(&attrs)?;
+    let vis = attrs.item_vis(&attrs.patch_vis, &vis);
+
+    let new_ident = generated_name(&attrs.patch, &ident, "Patch");
+    let field_enum = generated_name(&attrs.field_enum, &ident, "FieldEnum");
+    let derives = derive_attribute(&attrs.patch_derive);
+
+    // the patch is built from the variants of `StructFieldEnum`, so it leaves out the same fields
+    let fields = struct_fields(&data, &attrs, "StructPatch")?;
+    let fields = fields
+        .into_iter()
+        .filter(|field| !field.attrs.skip.enums)
+        .collect_vec();
+
+    let field_types = fields.iter().map(|field| field.ty()).collect_vec();
+    let field_names = fields.iter().map(|field| &field.ident).collect_vec();
+    let field_members = fields.iter().map(|field| &field.member).collect_vec();
+    let variants = fields
+        .iter()
+        .map(|field| field.variant_ident())
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let patch_generics = generics::filter_generics(&generics, field_types.iter().copied());
+    let (patch_impl_generics, patch_ty_generics, patch_where_clause) =
+        patch_generics.split_for_impl();
+
+    let is_empty = match fields.is_empty() {
+        true => quote!(true),
+        false => quote!(#(self.#field_names.is_none())&&*),
+    };
+
+    let result = quote! {
+        /// The fields to replace in a value, `None` keeping the current value
+        #derives
+        #vis struct #new_ident #patch_impl_generics #patch_where_clause {
+            #(pub #field_names: ::std::option::Option<#field_types>,)*
+        }
+        impl #patch_impl_generics ::std::default::Default for #new_ident #patch_ty_generics #patch_where_clause {
+            fn default() -> Self {
+                Self {
+                    #(#field_names: ::std::option::Option::None,)*
+                }
+            }
+        }
+        impl #patch_impl_generics #new_ident #patch_ty_generics #patch_where_clause {
+            /// returns if the patch doesn't replace any field
+            pub fn is_empty(&self) -> bool {
+                #is_empty
+            }
+
+            /// combines both patches, taking the fields set in `other` over the ones set in `self`
+            pub fn merge(self, other: Self) -> Self {
+                Self {
+                    #(#field_names: ::std::option::Option::or(other.#field_names, self.#field_names),)*
+                }
+            }
+        }
+        impl #patch_impl_generics ::std::iter::FromIterator<#field_enum #patch_ty_generics> for #new_ident #patch_ty_generics #patch_where_clause {
+            fn from_iter<__I: ::std::iter::IntoIterator<Item = #field_enum #patch_ty_generics>>(iter: __I) -> Self {
+                let mut patch = <Self as ::std::default::Default>::default();
+                for field in iter {
+                    match field {
+                        #(#field_enum::#variants(value) => patch.#field_names = ::std::option::Option::Some(value),)*
+                    }
+                }
+                patch
+            }
+        }
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// replaces every field that is set in `patch`
+            pub fn apply_patch(&mut self, patch: #new_ident #patch_ty_generics) {
+                #(if let ::std::option::Option::Some(value) = patch.#field_names {
+                    self.#field_members = value;
+                })*
+            }
+        }
+    };
+    //println!("{result}");
+    Ok(result)
+}
+
+/// The members of the fields the builders skip, and the values they are filled with instead
+fn skipped_fields<'f>(
+    skipped: impl IntoIterator<Item = &'f StructField<'f>>,
+) -> (Vec<syn::Member>, Vec<proc_macro2::TokenStream>) {
+    skipped
+        .into_iter()
+        .map(|field| {
+            let value = match &field.attrs.builder_default {
+                Some(default) => quote!(#default),
+                None => quote!(::std::default::Default::default()),
+            };
+            (field.member.clone(), value)
+        })
+        .unzip()
+}
+
+/**
+Requires `ty` to be `Clone`. The bound is higher-ranked, so that for a type that isn't `Clone` it
+only makes the method cloning it uncallable, instead of failing to compile right away
+*/
+fn clone_bound(ty: &Type) -> WherePredicate {
+    parse_quote!(for<'__clone> #ty: ::std::clone::Clone)
+}
+
+/// Requires `ty` to be `Default`, higher-ranked for the same reason as [`clone_bound`]
+fn default_bound(ty: &Type) -> WherePredicate {
+    parse_quote!(for<'__default> #ty: ::std::default::Default)
+}
+
+/// Requires `ty` to be `PartialEq`, higher-ranked for the same reason as [`clone_bound`]
+fn partial_eq_bound(ty: &Type) -> WherePredicate {
+    parse_quote!(for<'__eq> #ty: ::std::cmp::PartialEq)
+}
+
+/// The name of a generated item, the one given in the attribute or `ident` followed by `suffix`
+fn generated_name(custom: &Option<Ident>, ident: &Ident, suffix: &str) -> Ident {
+    match custom {
+        Some(custom) => custom.clone(),
+        None => format_ident!("{}{}", ident.unraw(), suffix, span = ident.span()),
+    }
+}
+
+/// The error returned when a generated enum holds another variant than the one converted into
+fn enum_error(vis: &syn::Visibility, error: &Ident) -> proc_macro2::TokenStream {
+    quote! {
+        /// The value was held by another variant than the one of the type it was converted into
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        #vis struct #error {
+            /// The variant of the type converted into
+            pub expected: &'static str,
+            /// The variant holding the value, `None` for a variant of a `value_enum` the struct
+            /// doesn't use
+            pub actual: ::std::option::Option<&'static str>,
+        }
+        impl ::std::fmt::Display for #error {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                match self.actual {
+                    ::std::option::Option::Some(actual) => {
+                        write!(f, "expected the variant `{}`, found `{}`", self.expected, actual)
+                    }
+                    ::std::option::Option::None => {
+                        write!(f, "expected the variant `{}`, found another one", self.expected)
+                    }
+                }
+            }
+        }
+        impl ::std::error::Error for #error {}
+    }
+}
+
+/**
+Matches `value` against the `variants` of `enum_path` and returns the name of the one it is in,
+wrapped in an `Option`. `open` is set for an existing enum that may have more variants
+*/
+fn variant_name<'v>(
+    enum_path: &proc_macro2::TokenStream,
+    variants: impl IntoIterator<Item = &'v Ident>,
+    open: bool,
+) -> proc_macro2::TokenStream {
+    let variants = variants.into_iter().collect_vec();
+    let names = variants.iter().map(|variant| variant.to_string());
+    let rest = open.then(|| {
+        quote! {
+            #[allow(unreachable_patterns)]
+            _ => ::std::option::Option::None,
+        }
+    });
+    quote! {
+        match value {
+            #(#enum_path::#variants(_) => ::std::option::Option::Some(#names),)*
+            #rest
+        }
+    }
+}
+
+/**
+The `is_*`, `as_*`, `as_*_mut`, `into_*` and `expect_*` methods of a generated enum, named after its
+`variants` in snake_case. `lifetime` is given for an enum holding `&'a mut` references to the
+`types` instead of the values themselves
+*/
+fn variant_accessors(
+    variants: &[Ident],
+    types: &[&Type],
+    lifetime: Option<&syn::Lifetime>,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let names = variants
+        .iter()
+        .map(|variant| variant.unraw().to_string())
+        .collect_vec();
+    let mut taken = std::collections::HashMap::new();
+    let methods = variants.iter().zip(types).zip(&names).map(|((variant, ty), name)| {
+        let snake = case::snake_case(name);
+        let is = format_ident!("is_{}", snake);
+        let as_ref = format_ident!("as_{}", snake);
+        let as_mut = format_ident!("as_{}_mut", snake);
+        let into = format_ident!("into_{}", snake);
+        let expect = format_ident!("expect_{}", snake);
+        for method in [&is, &as_ref, &as_mut, &into, &expect] {
+            if let Some(other) = taken.insert(method.to_string(), name) {
+                return Err(syn::Error::new_spanned(
+                    ty,
+                    format!(
+                        "the variants `{other}` and `{name}` would both have a method named `{method}`"
+                    ),
+                ));
+            }
+        }
+        let (held, borrowed, borrowed_mut) = match lifetime {
+            Some(lifetime) => (quote!(&#lifetime mut #ty), quote!(&**value), quote!(&mut **value)),
+            None => (quote!(#ty), quote!(value), quote!(value)),
+        };
+        let is_doc = format!("returns if the value is held by the variant `{name}`");
+        let as_doc = format!("returns the value of the variant `{name}`, or `None` for another one");
+        let as_mut_doc =
+            format!("returns the value of the variant `{name}` mutably, or `None` for another one");
+        let into_doc =
+            format!("moves the value of the variant `{name}` out, or returns `None` for another one");
+        let expect_doc = format!("returns the value of the variant `{name}`, panicking for another one");
+        Ok(quote! {
+            #[doc = #is_doc]
+            pub fn #is(&self) -> bool {
+                ::std::matches!(self, Self::#variant(_))
+            }
+
+            #[doc = #as_doc]
+            pub fn #as_ref(&self) -> ::std::option::Option<&#ty> {
+                match self {
+                    Self::#variant(value) => ::std::option::Option::Some(#borrowed),
+                    #[allow(unreachable_patterns)]
+                    _ => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = #as_mut_doc]
+            pub fn #as_mut(&mut self) -> ::std::option::Option<&mut #ty> {
+                match self {
+                    Self::#variant(value) => ::std::option::Option::Some(#borrowed_mut),
+                    #[allow(unreachable_patterns)]
+                    _ => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = #into_doc]
+            pub fn #into(self) -> ::std::option::Option<#held> {
+                match self {
+                    Self::#variant(value) => ::std::option::Option::Some(value),
+                    #[allow(unreachable_patterns)]
+                    _ => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = #expect_doc]
+            #[track_caller]
+            pub fn #expect(self) -> #held {
+                match self {
+                    Self::#variant(value) => value,
+                    #[allow(unreachable_patterns)]
+                    other => ::std::panic!(
+                        "expected the variant `{}`, found `{}`",
+                        #name,
+                        match other {
+                            #(Self::#variants(_) => #names,)*
+                        }
+                    ),
+                }
+            }
+        })
+    });
+    let methods = methods.collect::<syn::Result<Vec<_>>>()?;
+    Ok(quote!(#(#methods)*))
+}
+
+/// Turns the traits listed in a `*_derive(...)` option into a `#[derive(...)]`
+fn derive_attribute(traits: &[Path]) -> Option<proc_macro2::TokenStream> {
+    match traits.is_empty() {
+        true => None,
+        false => Some(quote!(#[derive(#(#traits),*)])),
+    }
+}
+
+/// The warnings for the deprecated attributes out of `read` used on the struct or its fields
+fn deprecations<'f>(
+    attrs: &ContainerAttrs,
+    fields: impl IntoIterator<Item = &'f StructField<'f>>,
+    read: &[&str],
+) -> proc_macro2::TokenStream {
+    let field_deprecations = fields
+        .into_iter()
+        .flat_map(|field| &field.attrs.deprecations);
+    let deprecations = attrs
+        .deprecations
+        .iter()
+        .chain(field_deprecations)
+        .filter(|deprecation| read.contains(&deprecation.attribute.as_str()))
+        .map(|deprecation| &deprecation.warning);
+    quote!(#(#deprecations)*)
+}
+
+/**
+The path of the `struct-tools` crate the derived traits live in, `None` unless it is named with
+`crate = "..."`. Finding it among the dependencies isn't enough, as it might only be a
+dev-dependency the code being derived in can't use
+*/
+fn runtime_crate(attrs: &ContainerAttrs) -> Option<proc_macro2::TokenStream> {
+    attrs.krate.as_ref().map(ToTokens::to_token_stream)
+}
+
+/// The associated type `name` of the `struct-tools` traits naming the generated enum `ident` holding references
+fn borrowing_enum_type(
+    name: &str,
+    ident: &Ident,
+    generics: &syn::Generics,
+    used_args: &[proc_macro2::TokenStream],
+    empty: bool,
+) -> proc_macro2::TokenStream {
+    let name = format_ident!("{}", name);
+    let (lifetime, _) = generics::with_lifetime(generics, "a");
+    // without any variants there is nothing to borrow, so the enum has no lifetime
+    let args = match empty {
+        true => quote!(<#(#used_args),*>),
+        false => quote!(<#lifetime, #(#used_args),*>),
+    };
+    quote! {
+        type #name<#lifetime> = #ident #args where Self: #lifetime;
+    }
 }
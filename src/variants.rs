@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use syn::{
+    ext::IdentExt, Expr, GenericArgument, Ident, Lit, Path, PathArguments, ReturnType, Type,
+    TypeParamBound,
+};
+
+use crate::{
+    case::RenameRule,
+    fields::{parse_ident, pretty_tokens, StructField},
+};
+
+/**
+The names of the variants holding `types` in the enums of `StructEnum`, e.g. `I32`, `VecString` or
+`RefStr`, unless a field of the type names it with `variant = "..."`
+
+Errors at a type whose variant would have the same name as the one of another type
+*/
+pub(crate) fn type_variants<'f>(
+    types: &[&Type],
+    fields: impl IntoIterator<Item = &'f StructField<'f>>,
+) -> syn::Result<Vec<Ident>> {
+    let fields = fields.into_iter().collect_vec();
+    let mut seen: HashMap<Ident, &Type> = HashMap::new();
+    types
+        .iter()
+        .map(|&ty| {
+            let variant = match custom_variant(ty, &fields)? {
+                Some(variant) => variant,
+                None => {
+                    let name = type_name(ty)?;
+                    parse_ident(&name).ok_or_else(|| {
+                        syn::Error::new_spanned(
+                            ty,
+                            format!("the generated variant name `{name}` is not a valid identifier, name it with `#[struct_tools(variant = \"...\")]`"),
+                        )
+                    })?
+                }
+            };
+            if let Some(other) = seen.insert(variant.clone(), ty) {
+                return Err(syn::Error::new_spanned(
+                    ty,
+                    format!(
+                        "`{}` and `{}` would both be held by the variant `{variant}`, name one of them with `#[struct_tools(variant = \"...\")]`",
+                        pretty_tokens(other),
+                        pretty_tokens(ty),
+                    ),
+                ));
+            }
+            Ok(variant)
+        })
+        .collect()
+}
+
+/// The variant named by the fields of type `ty`, erroring if they don't agree on one
+fn custom_variant(ty: &Type, fields: &[&StructField]) -> syn::Result<Option<Ident>> {
+    let mut variants = fields
+        .iter()
+        .filter(|field| field.ty() == ty)
+        .filter_map(|field| field.attrs.variant.as_ref());
+    let first = variants.next();
+    match variants.find(|variant| Some(*variant) != first) {
+        Some(other) => Err(syn::Error::new(
+            other.span(),
+            format!(
+                "`{}` is already held by the variant `{}`",
+                pretty_tokens(ty),
+                first.unwrap()
+            ),
+        )),
+        None => Ok(first.cloned()),
+    }
+}
+
+/**
+Names a type in PascalCase after the words it is written with, leaving out lifetimes and the
+modules of paths, e.g. `Vec<&'a str>` as `VecRefStr` and `[u8; 4]` as `Array4U8`
+*/
+fn type_name(ty: &Type) -> syn::Result<String> {
+    Ok(match ty {
+        Type::Path(ty) => {
+            let qself = match &ty.qself {
+                Some(qself) => type_name(&qself.ty)?,
+                None => String::new(),
+            };
+            qself + &path_name(&ty.path)?
+        }
+        Type::Reference(ty) => {
+            let mutability = if ty.mutability.is_some() { "Mut" } else { "" };
+            format!("Ref{mutability}{}", type_name(&ty.elem)?)
+        }
+        Type::Ptr(ty) => {
+            let mutability = if ty.mutability.is_some() { "Mut" } else { "" };
+            format!("Ptr{mutability}{}", type_name(&ty.elem)?)
+        }
+        Type::Array(ty) => format!("Array{}{}", expr_name(&ty.len)?, type_name(&ty.elem)?),
+        Type::Slice(ty) => format!("Slice{}", type_name(&ty.elem)?),
+        Type::Tuple(ty) if ty.elems.is_empty() => String::from("Unit"),
+        Type::Tuple(ty) => format!("Tuple{}", types_name(&ty.elems)?),
+        Type::BareFn(ty) => {
+            let inputs = ty.inputs.iter().map(|input| &input.ty);
+            format!("Fn{}{}", types_name(inputs)?, output_name(&ty.output)?)
+        }
+        Type::Never(_) => String::from("Never"),
+        Type::Paren(ty) => type_name(&ty.elem)?,
+        Type::Group(ty) => type_name(&ty.elem)?,
+        Type::TraitObject(ty) => format!("Dyn{}", bounds_name(&ty.bounds)?),
+        Type::ImplTrait(ty) => format!("Impl{}", bounds_name(&ty.bounds)?),
+        _ => {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "no variant name can be generated for this type, name it with `#[struct_tools(variant = \"...\")]`",
+            ))
+        }
+    })
+}
+
+fn types_name<'t>(types: impl IntoIterator<Item = &'t Type>) -> syn::Result<String> {
+    types.into_iter().map(type_name).collect()
+}
+
+/// Names a path after its last segment and the arguments given to it, e.g. `Fn(u8) -> bool` as `FnU8ToBool`
+fn path_name(path: &Path) -> syn::Result<String> {
+    let Some(segment) = path.segments.last() else {
+        return Ok(String::new());
+    };
+    let mut name = RenameRule::Pascal.apply(&segment.ident.unraw().to_string());
+    match &segment.arguments {
+        PathArguments::None => (),
+        PathArguments::AngleBracketed(arguments) => {
+            for argument in &arguments.args {
+                name += &match argument {
+                    GenericArgument::Type(ty) => type_name(ty)?,
+                    GenericArgument::Const(expr) => expr_name(expr)?,
+                    GenericArgument::AssocType(assoc) => type_name(&assoc.ty)?,
+                    _ => String::new(),
+                };
+            }
+        }
+        PathArguments::Parenthesized(arguments) => {
+            name += &types_name(&arguments.inputs)?;
+            name += &output_name(&arguments.output)?;
+        }
+    }
+    Ok(name)
+}
+
+fn output_name(output: &ReturnType) -> syn::Result<String> {
+    match output {
+        ReturnType::Default => Ok(String::new()),
+        ReturnType::Type(_, ty) => Ok(format!("To{}", type_name(ty)?)),
+    }
+}
+
+fn bounds_name<'b>(bounds: impl IntoIterator<Item = &'b TypeParamBound>) -> syn::Result<String> {
+    bounds
+        .into_iter()
+        .map(|bound| match bound {
+            TypeParamBound::Trait(bound) => path_name(&bound.path),
+            _ => Ok(String::new()),
+        })
+        .collect()
+}
+
+/// Names the length of an array or a const argument, e.g. `4` or `N`
+fn expr_name(expr: &Expr) -> syn::Result<String> {
+    match expr {
+        Expr::Lit(syn::ExprLit {
+            lit: Lit::Int(int), ..
+        }) => Ok(int.base10_digits().to_owned()),
+        Expr::Path(expr) => path_name(&expr.path),
+        Expr::Block(block) if block.block.stmts.len() == 1 => match &block.block.stmts[0] {
+            syn::Stmt::Expr(expr, None) => expr_name(expr),
+            _ => Ok(String::new()),
+        },
+        Expr::Paren(expr) => expr_name(&expr.expr),
+        Expr::Group(expr) => expr_name(&expr.expr),
+        _ => Err(syn::Error::new_spanned(
+            expr,
+            "no variant name can be generated for this length, name it with `#[struct_tools(variant = \"...\")]`",
+        )),
+    }
+}
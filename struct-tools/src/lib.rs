@@ -0,0 +1,269 @@
+/*!
+Traits implemented by the derives of [`struct_tools_derive`], which are re-exported here.
+
+The derives keep generating their inherent methods. When a struct names this crate with
+`#[struct_tools(crate = "struct_tools")]` they additionally implement the matching trait, so
+derived structs can be used generically:
+
+```rust
+use struct_tools::{FieldNames, StructIterTools};
+
+#[derive(StructIterTools)]
+#[struct_tools(fields, crate = "struct_tools")]
+pub struct Book {
+    title: String,
+    pages: u64,
+}
+
+fn dump<T: FieldNames>() -> String {
+    T::fields().join(", ")
+}
+
+assert_eq!(dump::<Book>(), "title, pages");
+```
+*/
+
+// lets the derives name this crate as `::struct_tools` in its own tests and examples
+extern crate self as struct_tools;
+
+pub use struct_tools_derive::*;
+
+use std::any::TypeId;
+use std::collections::BTreeMap;
+use std::sync::{Mutex, PoisonError};
+
+/// Describes a struct, generated by `StructIterTools` with `#[struct_tools(fields)]` as `STRUCT_INFO`
+#[derive(Debug, Clone, Copy)]
+pub struct StructInfo {
+    /// The name of the struct, e.g. `"Book"`
+    pub name: &'static str,
+    /// The path of the module the struct is declared in, as given by [`module_path!`]
+    pub module_path: &'static str,
+    /// The doc comments of the struct, one line each
+    pub docs: &'static str,
+    /// The fields of the struct, as returned by `field_infos`
+    pub fields: &'static [FieldInfo],
+}
+
+/// Describes a field of a struct, as part of its [`StructInfo`]
+#[derive(Debug, Clone, Copy)]
+pub struct FieldInfo {
+    /// The name the field is reported as, after `rename` and `rename_all`
+    pub name: &'static str,
+    /// The position of the field in the declaration of the struct, counting skipped fields as well
+    pub position: usize,
+    /// The type of the field as written in the declaration, e.g. `"Vec<String>"`
+    pub type_name: &'static str,
+    /// Returns the [`TypeId`] of the type of the field, `None` if the type mentions generic
+    /// parameters, as it might not be `'static` then
+    pub type_id: Option<fn() -> TypeId>,
+    /// The doc comments of the field, one line each
+    pub docs: &'static str,
+    /// The visibility of the field as written in the declaration, e.g. `"pub(crate)"`, empty for private ones
+    pub visibility: &'static str,
+    /// Whether the builders start out with a `builder(default = ...)` for the field
+    pub has_builder_default: bool,
+}
+
+impl FieldInfo {
+    /// The [`TypeId`] of the type of the field, `None` if the type mentions generic parameters
+    pub fn type_id(&self) -> Option<TypeId> {
+        self.type_id.map(|type_id| type_id())
+    }
+}
+
+/// Structs describing themselves and their fields, implemented by `StructIterTools` with `#[struct_tools(fields)]`
+pub trait HasStructInfo {
+    /// The description of the struct and its fields
+    const STRUCT_INFO: StructInfo;
+
+    /// returns the descriptions of the fields
+    fn field_infos() -> &'static [FieldInfo] {
+        Self::STRUCT_INFO.fields
+    }
+}
+
+/// Structs knowing the names of their fields, implemented by `StructIterTools` with `#[struct_tools(fields)]`
+pub trait FieldNames {
+    /// the names of the Structs fields, as returned by [`FieldNames::fields`] unless a field is flattened
+    const FIELD_NAMES: &'static [&'static str];
+
+    /// the number of the Structs fields
+    const FIELD_COUNT: usize = Self::FIELD_NAMES.len();
+
+    /// the number of names [`FieldNames::fields`] returns, counting the fields of a flattened field
+    const FLATTENED_FIELD_COUNT: usize = Self::FIELD_COUNT;
+
+    /// returns the names of the Structs fields
+    fn fields() -> Vec<String>;
+
+    /// returns the position of the field named `name` in [`FieldNames::FIELD_NAMES`], also accepting its aliases
+    fn field_index(name: &str) -> Option<usize>;
+
+    /// returns the position of the field named `name` in [`FieldNames::fields`], also accepting its
+    /// aliases and the paths of the fields of a flattened field
+    fn flattened_field_index(name: &str) -> Option<usize> {
+        Self::field_index(name)
+    }
+}
+
+/// Structs listing the values of their fields as `E`, implemented by `StructIterTools` with
+/// `#[struct_tools(values)]` for every `E` all field types convert into
+pub trait FieldValues<E> {
+    /// returns the values of this Instance
+    fn values(&self) -> Vec<E>;
+
+    /// returns a Vector of Tuples of the field and the values of this Instance
+    fn fields_and_values(&self) -> Vec<(String, E)>
+    where
+        Self: FieldNames,
+    {
+        Self::fields().into_iter().zip(self.values()).collect()
+    }
+}
+
+/// Structs listing references to their fields as `E`, implemented by `StructIterTools` with
+/// `#[struct_tools(values)]` for every `E` all field types convert into from references
+pub trait FieldValuesRef<'a, E> {
+    /// returns the values of this Instance, converted from references instead of clones
+    fn values_ref(&'a self) -> Vec<E>;
+}
+
+/// Structs moving the values out of their fields as `E`, implemented by `StructIterTools` with
+/// `#[struct_tools(values, into)]` for every `E` all field types convert into
+pub trait IntoFieldValues<E> {
+    /// returns the values of this Instance, moving them out instead of cloning them
+    fn into_values(self) -> Vec<E>;
+}
+
+/// Structs built back from their values, implemented by `StructIterTools` with
+/// `#[struct_tools(values)]` for every `E` converting into all field types
+pub trait FromFieldValues<E>: Sized {
+    /// The generated `{structname}FromValuesError`
+    type Error;
+
+    /// builds an Instance from its values in the order [`FieldValues::values`] returns them
+    fn from_values(values: impl IntoIterator<Item = E>) -> Result<Self, Self::Error>;
+}
+
+/// Gets to see every field of a struct, each with its own type, from `visit_fields`
+pub trait FieldVisitor {
+    /// called with the name and a reference to the value of each field
+    fn visit<T: 'static>(&mut self, name: &'static str, value: &T);
+}
+
+/// Gets to change every field of a struct, each with its own type, from `visit_fields_mut`
+pub trait FieldVisitorMut {
+    /// called with the name and a mutable reference to the value of each field
+    fn visit<T: 'static>(&mut self, name: &'static str, value: &mut T);
+}
+
+/// Hands the fields of a flattened field on to `visitor`, their names prefixed with `prefix`,
+/// e.g. `"address."`, used by the derives
+#[doc(hidden)]
+pub struct PrefixedVisitor<'v, V> {
+    pub prefix: &'static str,
+    pub visitor: &'v mut V,
+}
+
+impl<V: FieldVisitor> FieldVisitor for PrefixedVisitor<'_, V> {
+    fn visit<T: 'static>(&mut self, name: &'static str, value: &T) {
+        self.visitor.visit(prefixed_name(self.prefix, name), value)
+    }
+}
+
+impl<V: FieldVisitorMut> FieldVisitorMut for PrefixedVisitor<'_, V> {
+    fn visit<T: 'static>(&mut self, name: &'static str, value: &mut T) {
+        self.visitor.visit(prefixed_name(self.prefix, name), value)
+    }
+}
+
+/// Returns `name` with `prefix` in front of it, put together and leaked only the first time, as
+/// the names of the fields are few and the visitors keep them as `&'static str`
+fn prefixed_name(prefix: &'static str, name: &'static str) -> &'static str {
+    static NAMES: Mutex<BTreeMap<(&str, &str), &str>> = Mutex::new(BTreeMap::new());
+    let mut names = NAMES.lock().unwrap_or_else(PoisonError::into_inner);
+    names
+        .entry((prefix, name))
+        .or_insert_with(|| Box::leak(format!("{prefix}{name}").into_boxed_str()))
+}
+
+/// Structs handing their fields to visitors, implemented by `StructIterTools` with `#[struct_tools(values)]`
+pub trait VisitFields {
+    /// calls `visitor` with every field
+    fn visit_fields(&self, visitor: &mut impl FieldVisitor);
+
+    /// calls `visitor` with every field, mutably
+    fn visit_fields_mut(&mut self, visitor: &mut impl FieldVisitorMut);
+}
+
+/// Structs with an enum holding one variant per field, implemented by `StructFieldEnum`
+pub trait HasFieldEnum {
+    /// The generated `{structname}FieldEnum`
+    type FieldEnum;
+
+    /// returns a clone of every field wrapped in its variant
+    fn get_fields_enums(&self) -> Vec<Self::FieldEnum>;
+
+    /// returns the names of the variants
+    fn get_variants() -> Vec<&'static str>;
+}
+
+/// Structs with an enum borrowing one variant per field, implemented by `StructFieldEnumMut`
+pub trait HasFieldEnumMut {
+    /// The generated `{structname}FieldEnumMut`
+    type FieldEnumMut<'a>
+    where
+        Self: 'a;
+
+    /// returns a mutable reference to every field wrapped in its variant
+    fn get_fields_enums_mut(&mut self) -> Vec<Self::FieldEnumMut<'_>>;
+
+    /// returns the names of the variants
+    fn get_variants() -> Vec<&'static str>;
+}
+
+/// Structs with an enum borrowing one variant per field, implemented by `StructFieldEnumRef`
+pub trait HasFieldEnumRef {
+    /// The generated `{structname}FieldEnumRef`
+    type FieldEnumRef<'a>
+    where
+        Self: 'a;
+
+    /// returns a reference to every field wrapped in its variant
+    fn get_fields_enums_ref(&self) -> Vec<Self::FieldEnumRef<'_>>;
+
+    /// returns the names of the variants
+    fn get_variants() -> Vec<&'static str>;
+}
+
+/// Structs with an enum holding one variant per field type, implemented by `StructEnum`
+pub trait HasTypeEnum {
+    /// The generated `{structname}Enum`
+    type TypeEnum;
+
+    /// returns a clone of every field wrapped in the variant of its type
+    fn gets_enums(&self) -> Vec<Self::TypeEnum>;
+}
+
+/// Structs with an enum borrowing one variant per field type, implemented by `StructEnumMut`
+pub trait HasTypeEnumMut {
+    /// The generated `{structname}EnumMut`
+    type TypeEnumMut<'a>
+    where
+        Self: 'a;
+
+    /// returns a mutable reference to every field wrapped in the variant of its type
+    fn gets_enums_mut(&mut self) -> Vec<Self::TypeEnumMut<'_>>;
+}
+
+/// Structs with an enum borrowing one variant per field type, implemented by `StructEnumRef`
+pub trait HasTypeEnumRef {
+    /// The generated `{structname}EnumRef`
+    type TypeEnumRef<'a>
+    where
+        Self: 'a;
+
+    /// returns a reference to every field wrapped in the variant of its type
+    fn gets_enums_ref(&self) -> Vec<Self::TypeEnumRef<'_>>;
+}
@@ -0,0 +1,334 @@
+#![allow(unused)]
+use std::collections::BTreeMap;
+use struct_tools::{
+    FieldNames, FieldValues, FieldVisitor, FromFieldValues, StructEnum, StructFieldEnum,
+    StructFieldEnumRef, StructIterTools, VisitFields,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Text(String),
+    Number(u32),
+}
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::Text(value)
+    }
+}
+impl From<u32> for Value {
+    fn from(value: u32) -> Self {
+        Value::Number(value)
+    }
+}
+impl TryFrom<Value> for String {
+    type Error = Value;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Text(text) => Ok(text),
+            value => Err(value),
+        }
+    }
+}
+impl TryFrom<Value> for u32 {
+    type Error = Value;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Number(number) => Ok(number),
+            value => Err(value),
+        }
+    }
+}
+
+/// Any field printed, converted from a reference
+#[derive(Debug, PartialEq)]
+pub struct Shown(String);
+
+impl<T: ToString> From<&T> for Shown {
+    fn from(value: &T) -> Self {
+        Shown(value.to_string())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, StructIterTools)]
+#[struct_tools(fields, values, into, crate = "struct_tools")]
+pub struct Address {
+    city: String,
+    zip: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, StructIterTools)]
+#[struct_tools(fields, values, into, rename_all = "camelCase", crate = "struct_tools")]
+pub struct Customer {
+    full_name: String,
+    #[struct_tools(flatten)]
+    home_address: Address,
+    #[struct_tools(skip)]
+    cache: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq, StructIterTools)]
+#[struct_tools(fields, values, flatten_separator = "/", crate = "struct_tools")]
+pub struct Order {
+    id: u32,
+    #[struct_tools(flatten)]
+    customer: Customer,
+}
+
+// the derives other than `StructIterTools` keep a flattened field as a whole
+#[derive(
+    Clone, Debug, PartialEq, StructIterTools, StructEnum, StructFieldEnum, StructFieldEnumRef,
+)]
+#[struct_tools(
+    fields,
+    values,
+    enum_derive(Debug, PartialEq),
+    ref_enum_derive(Debug, PartialEq),
+    crate = "struct_tools"
+)]
+pub struct Shipment {
+    id: u32,
+    #[struct_tools(flatten)]
+    address: Address,
+}
+
+/// Collects the names of the fields it visits
+#[derive(Default)]
+struct Names(Vec<&'static str>);
+
+impl FieldVisitor for Names {
+    fn visit<T: 'static>(&mut self, name: &'static str, _: &T) {
+        self.0.push(name);
+    }
+}
+
+fn customer() -> Customer {
+    Customer {
+        full_name: String::from("Ada"),
+        home_address: Address {
+            city: String::from("London"),
+            zip: 1815,
+        },
+        cache: Vec::new(),
+    }
+}
+
+#[test]
+fn fields() {
+    assert_eq!(
+        Customer::fields(),
+        vec!["fullName", "homeAddress.city", "homeAddress.zip"]
+    );
+    assert_eq!(
+        <Order as FieldNames>::fields(),
+        vec![
+            "id",
+            "customer/fullName",
+            "customer/homeAddress.city",
+            "customer/homeAddress.zip"
+        ]
+    );
+    // the constants keep describing the struct's own fields, the flattened ones counted separately
+    assert_eq!(Customer::FIELD_NAMES, ["fullName", "homeAddress"]);
+    assert_eq!(Customer::FIELD_COUNT, Customer::FIELD_NAMES.len());
+    assert_eq!(Customer::FLATTENED_FIELD_COUNT, Customer::fields().len());
+    assert_eq!(<Order as FieldNames>::FIELD_COUNT, 2);
+    assert_eq!(<Order as FieldNames>::FLATTENED_FIELD_COUNT, 4);
+    assert_eq!(<Address as FieldNames>::FLATTENED_FIELD_COUNT, 2);
+}
+
+// still a `const fn` with a flattened field
+const ADDRESS_INDEX: Option<usize> = Customer::field_index("homeAddress");
+
+#[test]
+fn field_index() {
+    assert_eq!(ADDRESS_INDEX, Some(1));
+    assert_eq!(Customer::FIELD_NAMES[ADDRESS_INDEX.unwrap()], "homeAddress");
+    assert_eq!(Customer::field_index("homeAddress.zip"), None);
+    assert_eq!(Order::field_index("customer"), Some(1));
+}
+
+#[test]
+fn flattened_field_index() {
+    assert_eq!(Customer::flattened_field_index("fullName"), Some(0));
+    assert_eq!(Customer::flattened_field_index("homeAddress.zip"), Some(2));
+    assert_eq!(Customer::flattened_field_index("homeAddress"), None);
+    assert_eq!(Customer::flattened_field_index("homeAddress.street"), None);
+    assert_eq!(
+        Order::flattened_field_index("customer/homeAddress.city"),
+        Some(2)
+    );
+    assert_eq!(
+        <Order as FieldNames>::flattened_field_index("customer/fullName"),
+        Some(1)
+    );
+    assert_eq!(
+        <Address as FieldNames>::flattened_field_index("zip"),
+        Some(1)
+    );
+    let fields = Order::fields();
+    assert_eq!(
+        fields[Order::flattened_field_index("customer/homeAddress.zip").unwrap()],
+        "customer/homeAddress.zip"
+    );
+}
+
+#[test]
+fn values() {
+    assert_eq!(
+        customer().values::<Value>(),
+        vec![
+            Value::Text(String::from("Ada")),
+            Value::Text(String::from("London")),
+            Value::Number(1815),
+        ]
+    );
+    let order = Order {
+        id: 7,
+        customer: customer(),
+    };
+    assert_eq!(FieldValues::<Value>::values(&order).len(), 4);
+}
+
+#[test]
+fn fields_and_values() {
+    let map: BTreeMap<String, Value> = customer().to_map();
+    assert_eq!(map["homeAddress.zip"], Value::Number(1815));
+    assert_eq!(
+        customer().fields_and_values::<Value>()[1],
+        (
+            String::from("homeAddress.city"),
+            Value::Text(String::from("London"))
+        )
+    );
+}
+
+#[test]
+fn values_ref_and_into_values() {
+    assert_eq!(
+        customer().values_ref::<Shown>(),
+        vec![
+            Shown(String::from("Ada")),
+            Shown(String::from("London")),
+            Shown(String::from("1815")),
+        ]
+    );
+    assert_eq!(
+        customer().into_values::<Value>(),
+        customer().values::<Value>()
+    );
+}
+
+#[test]
+fn visit_fields() {
+    let order = Order {
+        id: 7,
+        customer: customer(),
+    };
+    let mut names = Names::default();
+    order.visit_fields(&mut names);
+    assert_eq!(names.0, <Order as FieldNames>::fields());
+
+    // the prefixed names are only put together once
+    let mut again = Names::default();
+    order.visit_fields(&mut again);
+    assert!(std::ptr::eq(names.0[3], again.0[3]));
+}
+
+#[test]
+fn from_values() {
+    assert_eq!(
+        Customer::from_values(customer().values::<Value>()),
+        Ok(customer())
+    );
+    let order = Order {
+        id: 7,
+        customer: customer(),
+    };
+    let values = FieldValues::<Value>::values(&order);
+    assert_eq!(
+        <Order as FromFieldValues<Value>>::from_values(values),
+        Ok(Order {
+            id: 7,
+            customer: customer(),
+        })
+    );
+
+    // the city is a number, so the address can't be built
+    let error = Customer::from_values([
+        Value::Text(String::from("Ada")),
+        Value::Number(1),
+        Value::Number(1815),
+    ])
+    .unwrap_err();
+    assert_eq!(error.wrong_variant, vec!["homeAddress"]);
+    let error = Customer::from_values([Value::Text(String::from("Ada"))]).unwrap_err();
+    assert_eq!(error.missing, vec!["homeAddress"]);
+}
+
+#[test]
+fn from_fields_and_values() {
+    let order = Order {
+        id: 7,
+        customer: customer(),
+    };
+    let map: BTreeMap<String, Value> = order.to_map();
+    assert_eq!(Order::try_from_map(map), Ok(order));
+
+    let error = Customer::from_fields_and_values([
+        ("fullName", Value::Text(String::from("Ada"))),
+        ("homeAddress.city", Value::Text(String::from("London"))),
+        ("homeAddress.city", Value::Text(String::from("Paris"))),
+        ("homeAddress.street", Value::Text(String::new())),
+    ])
+    .unwrap_err();
+    assert_eq!(error.duplicated, vec!["homeAddress"]);
+    assert!(error.missing.is_empty());
+    assert_eq!(error.unknown, vec![String::from("homeAddress.street")]);
+
+    // in the order of the fields, the flattened one included
+    let error =
+        Order::from_fields_and_values([("customer/fullName", Value::Number(1))]).unwrap_err();
+    assert_eq!(error.missing, vec!["id", "customer"]);
+}
+
+#[test]
+fn field_enums() {
+    let address = Address {
+        city: String::from("Oslo"),
+        zip: 150,
+    };
+    let mut shipment = Shipment {
+        id: 1,
+        address: address.clone(),
+    };
+    assert_eq!(
+        Shipment::fields(),
+        vec!["id", "address.city", "address.zip"]
+    );
+    assert_eq!(
+        shipment.get_field("address"),
+        Some(ShipmentFieldEnumRef::Address(&address))
+    );
+    assert_eq!(shipment.get_field("address.city"), None);
+
+    let moved = Address {
+        city: String::from("Bergen"),
+        zip: 5003,
+    };
+    assert_eq!(
+        shipment.set_field_by_name("address", ShipmentEnum::Address(moved.clone())),
+        Ok(())
+    );
+    assert_eq!(
+        shipment.set_field_by_name("address.city", ShipmentEnum::U32(0)),
+        Err(ShipmentFieldError::UnknownField(String::from(
+            "address.city"
+        )))
+    );
+    assert_eq!(
+        shipment.get_fields_enums(),
+        vec![ShipmentFieldEnum::Id(1), ShipmentFieldEnum::Address(moved)]
+    );
+}
@@ -0,0 +1,76 @@
+#![allow(unused)]
+use std::any::TypeId;
+use struct_tools::{FieldInfo, HasStructInfo, StructBuilder, StructIterTools};
+
+/// A book on a shelf
+///
+/// Used by the admin UI
+#[derive(StructIterTools, StructBuilder)]
+#[struct_tools(fields, rename_all = "camelCase", crate = "struct_tools")]
+pub struct Book<'a, T> {
+    /// The title, as printed on the cover
+    pub title: String,
+    #[struct_tools(builder(default = Vec::new()))]
+    pub(crate) page_numbers: Vec<u32>,
+    #[struct_tools(skip)]
+    cache: Option<T>,
+    shelf: &'a str,
+    extra: Option<T>,
+    callback: fn(u8) -> u8,
+}
+
+#[derive(StructIterTools)]
+#[struct_tools(fields, crate = "struct_tools")]
+pub struct Pair(pub u8, u16);
+
+fn names<T: HasStructInfo>() -> Vec<&'static str> {
+    T::field_infos().iter().map(|info| info.name).collect()
+}
+
+#[test]
+fn struct_info() {
+    let info = Book::<u8>::STRUCT_INFO;
+    assert_eq!(info.name, "Book");
+    assert_eq!(info.module_path, "info");
+    assert_eq!(info.docs, "A book on a shelf\n\nUsed by the admin UI");
+    assert_eq!(info.fields.len(), 5);
+    assert_eq!(
+        names::<Book<u8>>(),
+        vec!["title", "pageNumbers", "shelf", "extra", "callback"]
+    );
+}
+
+#[test]
+fn field_infos() {
+    let fields = Book::<u8>::field_infos();
+
+    let title = &fields[0];
+    assert_eq!(title.position, 0);
+    assert_eq!(title.type_name, "String");
+    assert_eq!(title.type_id(), Some(TypeId::of::<String>()));
+    assert_eq!(title.docs, "The title, as printed on the cover");
+    assert_eq!(title.visibility, "pub");
+    assert!(!title.has_builder_default);
+
+    let page_numbers = &fields[1];
+    assert_eq!(page_numbers.type_name, "Vec<u32>");
+    assert_eq!(page_numbers.visibility, "pub(crate)");
+    assert!(page_numbers.has_builder_default);
+
+    // the skipped `cache` still counts for the positions
+    let shelf = &fields[2];
+    assert_eq!(shelf.position, 3);
+    assert_eq!(shelf.type_name, "&'a str");
+    assert_eq!(shelf.type_id(), None);
+    assert_eq!(shelf.visibility, "");
+
+    assert_eq!(fields[3].type_name, "Option<T>");
+    assert_eq!(fields[3].type_id(), None);
+    assert_eq!(fields[4].type_name, "fn(u8) -> u8");
+    assert_eq!(fields[4].type_id(), Some(TypeId::of::<fn(u8) -> u8>()));
+
+    let pair = Pair::field_infos();
+    assert_eq!(pair[0].name, "0");
+    assert_eq!(pair[1].position, 1);
+    assert_eq!(pair[1].type_id(), Some(TypeId::of::<u16>()));
+}
@@ -0,0 +1,186 @@
+#![allow(unused)]
+use std::fmt::Debug;
+use struct_tools::{
+    FieldNames, FieldValues, HasFieldEnum, HasFieldEnumMut, HasFieldEnumRef, HasTypeEnum,
+    HasTypeEnumMut, HasTypeEnumRef, StructEnum, StructEnumMut, StructEnumRef, StructFieldEnum,
+    StructFieldEnumMut, StructFieldEnumRef, StructIterTools,
+};
+
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    StructIterTools,
+    StructEnum,
+    StructEnumMut,
+    StructEnumRef,
+    StructFieldEnum,
+    StructFieldEnumMut,
+    StructFieldEnumRef,
+)]
+#[struct_tools(
+    fields,
+    values,
+    enum_derive(Debug, Clone, PartialEq),
+    mut_enum_derive(Debug),
+    crate = "struct_tools"
+)]
+pub struct Book {
+    title: String,
+    pages: u64,
+}
+
+#[derive(Debug, StructIterTools, StructFieldEnum, StructFieldEnumMut)]
+#[struct_tools(fields, values, enum_derive(Debug, PartialEq), crate = "struct_tools")]
+pub struct Page<'a, T: Clone> {
+    number: u32,
+    items: &'a [T],
+}
+
+#[derive(
+    StructIterTools,
+    StructEnumMut,
+    StructEnumRef,
+    StructFieldEnum,
+    StructFieldEnumMut,
+    StructFieldEnumRef,
+)]
+#[struct_tools(fields, values, crate = "struct_tools")]
+pub struct Unit;
+
+mod renamed {
+    use struct_tools::StructIterTools;
+
+    #[derive(StructIterTools)]
+    #[struct_tools(fields, crate = "::struct_tools")]
+    pub struct Point(pub i32, pub i32);
+}
+
+fn names<T: FieldNames>() -> Vec<String> {
+    T::fields()
+}
+
+fn dump<T: FieldNames + FieldValues<E>, E: Debug>(value: &T) -> Vec<String> {
+    value
+        .fields_and_values()
+        .into_iter()
+        .map(|(field, value)| format!("{field}: {value:?}"))
+        .collect()
+}
+
+fn field_enums<T: HasFieldEnum>(value: &T) -> (Vec<&'static str>, Vec<T::FieldEnum>) {
+    (T::get_variants(), value.get_fields_enums())
+}
+
+fn touch_fields<T: HasFieldEnumMut>(value: &mut T) -> usize {
+    value.get_fields_enums_mut().len()
+}
+
+#[test]
+fn field_names() {
+    assert_eq!(names::<Book>(), vec!["title", "pages"]);
+    assert_eq!(names::<Page<u8>>(), vec!["number", "items"]);
+    assert_eq!(names::<renamed::Point>(), vec!["0", "1"]);
+    assert!(names::<Unit>().is_empty());
+    assert_eq!(<Book as FieldNames>::FIELD_NAMES, ["title", "pages"]);
+    assert_eq!(<Page<u8> as FieldNames>::FIELD_COUNT, 2);
+    assert_eq!(<Unit as FieldNames>::FIELD_COUNT, 0);
+}
+
+#[test]
+fn field_values() {
+    let book = Book {
+        title: "Title".to_owned(),
+        pages: 100,
+    };
+    assert_eq!(
+        dump::<_, BookEnum>(&book),
+        vec!["title: String(\"Title\")", "pages: U64(100)"]
+    );
+    assert_eq!(
+        FieldValues::<BookEnum>::values(&book),
+        vec![BookEnum::String("Title".to_owned()), BookEnum::U64(100)]
+    );
+    assert!(FieldValues::<()>::values(&Unit).is_empty());
+}
+
+#[test]
+fn field_enums_generically() {
+    let book = Book {
+        title: "Title".to_owned(),
+        pages: 100,
+    };
+    let (variants, enums) = field_enums(&book);
+    assert_eq!(variants, vec!["Title", "Pages"]);
+    assert_eq!(
+        enums,
+        vec![
+            BookFieldEnum::Title("Title".to_owned()),
+            BookFieldEnum::Pages(100)
+        ]
+    );
+
+    let items = [1u8, 2];
+    let page = Page {
+        number: 1,
+        items: &items,
+    };
+    let (_, enums) = field_enums(&page);
+    assert_eq!(
+        enums,
+        vec![PageFieldEnum::Number(1), PageFieldEnum::Items(&items)]
+    );
+    assert!(field_enums(&Unit).1.is_empty());
+}
+
+#[test]
+fn field_enums_mut_generically() {
+    let mut book = Book::default();
+    assert_eq!(touch_fields(&mut book), 2);
+    assert_eq!(touch_fields(&mut Unit), 0);
+    assert_eq!(
+        <Book as HasFieldEnumMut>::get_variants(),
+        vec!["Title", "Pages"]
+    );
+}
+
+#[test]
+fn type_enums_generically() {
+    fn all<T: HasTypeEnum>(value: &T) -> Vec<T::TypeEnum> {
+        value.gets_enums()
+    }
+    fn all_mut<T: HasTypeEnumMut>(value: &mut T) -> usize {
+        value.gets_enums_mut().len()
+    }
+
+    let mut book = Book {
+        title: "Title".to_owned(),
+        pages: 100,
+    };
+    assert_eq!(
+        all(&book),
+        vec![BookEnum::String("Title".to_owned()), BookEnum::U64(100)]
+    );
+    assert_eq!(all_mut(&mut book), 2);
+    assert_eq!(all_mut(&mut Unit), 0);
+}
+
+#[test]
+fn ref_enums_generically() {
+    fn types<T: HasTypeEnumRef>(value: &T) -> usize {
+        value.gets_enums_ref().len()
+    }
+    fn fields<T: HasFieldEnumRef>(value: &T) -> usize {
+        value.get_fields_enums_ref().len()
+    }
+
+    let book = Book::default();
+    assert_eq!(types(&book), 2);
+    assert_eq!(fields(&book), 2);
+    assert_eq!(types(&Unit), 0);
+    assert_eq!(
+        <Book as HasFieldEnumRef>::get_variants(),
+        vec!["Title", "Pages"]
+    );
+}
@@ -0,0 +1,107 @@
+#![allow(unused)]
+use std::any::{Any, TypeId};
+use struct_tools::{FieldVisitor, FieldVisitorMut, StructIterTools, VisitFields};
+
+type Id = u64;
+
+// `Id` and `u64` are the same type, which a value enum couldn't tell apart
+#[derive(Default, StructIterTools)]
+#[struct_tools(values, rename_all = "camelCase", crate = "struct_tools")]
+pub struct Order {
+    order_id: Id,
+    amount: u64,
+    customer: String,
+    #[struct_tools(skip)]
+    cache: Vec<u8>,
+}
+
+#[derive(StructIterTools)]
+#[struct_tools(values, crate = "struct_tools")]
+pub enum Event {
+    Paid { amount: u64 },
+    Note(String),
+}
+
+#[derive(StructIterTools)]
+#[struct_tools(values, crate = "struct_tools")]
+pub struct Labelled<'a, T> {
+    label: &'a str,
+    value: T,
+}
+
+/// Writes every field as `name=value`, as far as it knows how to print the type
+#[derive(Default)]
+struct Printer(Vec<String>);
+
+impl FieldVisitor for Printer {
+    fn visit<T: 'static>(&mut self, name: &'static str, value: &T) {
+        let value = value as &dyn Any;
+        let printed = if let Some(number) = value.downcast_ref::<u64>() {
+            number.to_string()
+        } else if let Some(text) = value.downcast_ref::<String>() {
+            format!("{text:?}")
+        } else if let Some(text) = value.downcast_ref::<&str>() {
+            format!("{text:?}")
+        } else {
+            String::from("?")
+        };
+        self.0.push(format!("{name}={printed}"));
+    }
+}
+
+/// Doubles every `u64`
+struct Doubler;
+
+impl FieldVisitorMut for Doubler {
+    fn visit<T: 'static>(&mut self, _: &'static str, value: &mut T) {
+        if let Some(number) = (value as &mut dyn Any).downcast_mut::<u64>() {
+            *number *= 2;
+        }
+    }
+}
+
+fn print(value: &impl VisitFields) -> Vec<String> {
+    let mut printer = Printer::default();
+    value.visit_fields(&mut printer);
+    printer.0
+}
+
+#[test]
+fn visit_fields() {
+    let order = Order {
+        order_id: 7,
+        amount: 100,
+        customer: String::from("Ada"),
+        cache: vec![1],
+    };
+    assert_eq!(
+        print(&order),
+        vec!["orderId=7", "amount=100", "customer=\"Ada\""]
+    );
+    assert_eq!(print(&Event::Paid { amount: 3 }), vec!["amount=3"]);
+    assert_eq!(
+        print(&Event::Note(String::from("late"))),
+        vec!["0=\"late\""]
+    );
+
+    let labelled = Labelled {
+        label: "size",
+        value: 5u64,
+    };
+    assert_eq!(print(&labelled), vec!["label=\"size\"", "value=5"]);
+}
+
+#[test]
+fn visit_fields_mut() {
+    let mut order = Order {
+        order_id: 7,
+        amount: 100,
+        ..Order::default()
+    };
+    order.visit_fields_mut(&mut Doubler);
+    assert_eq!((order.order_id, order.amount), (14, 200));
+
+    let mut event = Event::Paid { amount: 3 };
+    event.visit_fields_mut(&mut Doubler);
+    assert!(matches!(event, Event::Paid { amount: 6 }));
+}
@@ -0,0 +1,77 @@
+#![allow(unused)]
+use struct_tools_derive::{StructEnum, StructEnumMut, StructFieldEnum, StructFieldEnumMut};
+
+#[derive(Debug, Default, StructEnum, StructEnumMut, StructFieldEnum, StructFieldEnumMut)]
+pub struct Book {
+    title: String,
+    pages: u64,
+    tags: Option<Vec<String>>,
+    date_time: u64,
+}
+
+// a single variant leaves no other one to fall back to
+#[derive(StructEnum, StructFieldEnum)]
+pub struct Wrapper<T>(T);
+
+#[test]
+fn enum_accessors() {
+    let mut value = BookEnum::String("title".to_owned());
+    assert!(value.is_string());
+    assert!(!value.is_u64());
+    assert_eq!(value.as_string(), Some(&"title".to_owned()));
+    assert_eq!(value.as_u64(), None);
+    value.as_string_mut().unwrap().push('!');
+    assert_eq!(value.into_string(), Some("title!".to_owned()));
+
+    let tags = BookEnum::OptionVecString(None);
+    assert_eq!(tags.expect_option_vec_string(), None);
+    assert_eq!(BookEnum::U64(3).into_option_vec_string(), None);
+}
+
+#[test]
+#[should_panic(expected = "expected the variant `String`, found `U64`")]
+fn enum_expect_other_variant() {
+    BookEnum::U64(3).expect_string();
+}
+
+#[test]
+fn field_enum_accessors() {
+    let mut value = BookFieldEnum::DateTime(7);
+    assert!(value.is_date_time());
+    assert!(!value.is_pages());
+    *value.as_date_time_mut().unwrap() += 1;
+    assert_eq!(value.as_date_time(), Some(&8));
+    assert_eq!(value.into_pages(), None);
+    assert_eq!(BookFieldEnum::Pages(100).expect_pages(), 100);
+    assert_eq!(WrapperFieldEnum::Field0('a').expect_field0(), 'a');
+    assert_eq!(WrapperEnum::T(1).into_t(), Some(1));
+}
+
+#[test]
+#[should_panic(expected = "expected the variant `Title`, found `Tags`")]
+fn field_enum_expect_other_variant() {
+    BookFieldEnum::Tags(None).expect_title();
+}
+
+#[test]
+fn mut_enum_accessors() {
+    let mut book = Book::default();
+    for mut value in book.gets_enums_mut() {
+        if let Some(number) = value.as_u64_mut() {
+            *number += 1;
+        }
+        if value.is_string() {
+            value.expect_string().push_str("title");
+        }
+    }
+    for value in book.get_fields_enums_mut() {
+        if value.is_tags() {
+            assert_eq!(value.as_tags(), Some(&None));
+        }
+        if let Some(pages) = value.into_pages() {
+            *pages *= 10;
+        }
+    }
+    assert_eq!(book.title, "title");
+    assert_eq!((book.pages, book.date_time), (10, 1));
+}
@@ -1,11 +1,11 @@
-#![allow(unused)]
+#![allow(unused, clippy::useless_vec)]
 use itertools::Itertools;
 use std::fmt::Display;
 use struct_tools_derive::{
     StructBuilder, StructEnum, StructEnumMut, StructFieldEnum, StructFieldEnumMut, StructIterTools,
 };
 
-// FIXME!: This currently raises an error with the StructEnums for conflicting impl of From<u64> if put in books if field
+// a field of this type next to a `u64` needs `#[struct_tools(skip(from))]`, see tests/value_enum.rs
 pub type Id = u64;
 
 #[derive(
@@ -20,22 +20,26 @@ pub type Id = u64;
     StructFieldEnumMut,
     StructBuilder,
 )]
-#[StructFields]
-#[StructValues]
-#[EnumDerive(Debug, Clone)]
-#[MutEnumDerive(Debug)]
+#[struct_tools(fields, values, enum_derive(Debug, Clone), mut_enum_derive(Debug))]
 pub struct Book {
     id: u64,
     title: String,
-    #[builder_default(0)]
+    #[struct_tools(builder(default = 0))]
     pages: u64,
     author: String,
-    #[builder_default(None)]
+    #[struct_tools(builder(default = None))]
     inspirations: Option<Vec<String>>,
     date_time_: u64,
     tuple: (u8, u8),
 }
 
+// only `StructEnumMut`, whose conversions have an error of their own
+#[derive(Default, StructEnumMut)]
+pub struct Counter {
+    count: u64,
+    label: String,
+}
+
 #[derive(Debug)]
 pub enum BookEnumTest {
     U64(u64),
@@ -69,6 +73,8 @@ impl From<Option<Vec<String>>> for BookEnumTest {
     }
 }
 
+#[derive(StructIterTools, StructFieldEnum, StructFieldEnumMut)]
+#[struct_tools(fields)]
 pub struct BookWithLifetime<'a> {
     test: &'a mut String,
 }
@@ -151,18 +157,72 @@ fn enum_try_into_test() {
         .into_iter()
         .map(TryInto::<String>::try_into)
         .collect_vec();
+    let found = |actual| {
+        Err(BookEnumError {
+            expected: "String",
+            actual: Some(actual),
+        })
+    };
     let assert = vec![
-        Err(()),
+        found("U64"),
         Ok("".to_owned()),
-        Err(()),
+        found("U64"),
         Ok("".to_owned()),
-        Err(()),
-        Err(()),
-        Err(()),
+        found("OptionVecString"),
+        found("U64"),
+        found("TupleU8U8"),
     ];
     assert_eq!(assert, test)
 }
 
+#[test]
+fn enum_try_from_ref_and_mut_test() {
+    let value = BookEnum::String("title".to_owned());
+    assert_eq!(<&String>::try_from(&value), Ok(&"title".to_owned()));
+    assert_eq!(
+        <&u64>::try_from(&value),
+        Err(BookEnumError {
+            expected: "U64",
+            actual: Some("String"),
+        })
+    );
+
+    let mut book = Book::default();
+    for value in book.gets_enums_mut() {
+        if let Ok(pages) = <&mut u64>::try_from(value) {
+            *pages += 1;
+        }
+    }
+    assert_eq!((book.id, book.pages, book.date_time_), (1, 1, 1));
+
+    let mut tuple = (1, 2);
+    let error = <&mut u64>::try_from(BookEnumMut::TupleU8U8(&mut tuple)).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "expected the variant `U64`, found `TupleU8U8`"
+    );
+}
+
+#[test]
+fn enum_mut_alone_test() {
+    let mut counter = Counter::default();
+    for value in counter.gets_enums_mut() {
+        if let Ok(count) = <&mut u64>::try_from(value) {
+            *count += 1;
+        }
+    }
+    assert_eq!(counter.count, 1);
+
+    let mut label = String::new();
+    assert_eq!(
+        <&mut u64>::try_from(CounterEnumMut::String(&mut label)),
+        Err(CounterEnumMutError {
+            expected: "U64",
+            actual: Some("String"),
+        })
+    );
+}
+
 #[test]
 fn field_enum_test() {
     let test = BookFieldEnum::Id(1);
@@ -188,7 +248,7 @@ fn enum_values_test() {
         BookEnum::String("me".to_string()),
         BookEnum::OptionVecString(None),
         BookEnum::U64(0),
-        BookEnum::u8u8((0, 0)),
+        BookEnum::TupleU8U8((0, 0)),
     ];
     let book_values: Vec<BookEnum> = book.values::<BookEnum>();
     //println!("fields: {book_values:?}");
@@ -274,3 +334,16 @@ fn field_enum_mut_test() {
     println!("after: {:?}", book);
     assert_ne!(old_book, book)
 }
+
+#[test]
+fn lifetime_test() {
+    let mut title = "before".to_owned();
+    let mut book = BookWithLifetime { test: &mut title };
+    assert_eq!(BookWithLifetime::fields(), vec!["test"]);
+    for field in book.get_fields_enums_mut() {
+        match field {
+            BookWithLifetimeFieldEnumMut::Test(test) => **test = "after".to_owned(),
+        }
+    }
+    assert_eq!(title, "after")
+}
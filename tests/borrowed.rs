@@ -0,0 +1,176 @@
+#![allow(unused)]
+use std::sync::Mutex;
+use struct_tools_derive::{StructEnum, StructEnumRef, StructFieldEnumRef, StructIterTools};
+
+// `Mutex` is neither `Clone` nor `PartialEq`, so it can only be borrowed
+#[derive(StructIterTools, StructEnumRef, StructFieldEnumRef)]
+#[struct_tools(values, ref_enum_derive(Debug))]
+pub struct Document {
+    title: String,
+    tags: Vec<String>,
+    lock: Mutex<u8>,
+    #[struct_tools(skip)]
+    cache: Vec<u8>,
+}
+
+#[derive(StructEnumRef, StructFieldEnumRef)]
+#[struct_tools(ref_enum_derive(Debug, PartialEq))]
+pub struct Pair<T>(T, u8);
+
+#[derive(StructEnumRef, StructFieldEnumRef)]
+#[struct_tools(ref_enum_derive(Debug, PartialEq))]
+pub enum Event {
+    Opened { path: String },
+    Moved(String, String),
+    Closed,
+}
+
+#[derive(StructFieldEnumRef)]
+pub struct Unit;
+
+#[derive(StructEnumRef, StructFieldEnumRef)]
+pub enum Never {}
+
+#[derive(StructEnum, StructEnumRef)]
+#[struct_tools(enum_derive(Debug, PartialEq), ref_enum_derive(Debug, PartialEq))]
+pub struct Note {
+    text: String,
+    line: u32,
+}
+
+#[derive(Debug)]
+pub enum Value<'a> {
+    Text(&'a str),
+    Tags(usize),
+    Locked(&'a Mutex<u8>),
+}
+impl<'a> From<&'a String> for Value<'a> {
+    fn from(value: &'a String) -> Self {
+        Value::Text(value)
+    }
+}
+impl<'a> From<&'a Vec<String>> for Value<'a> {
+    fn from(value: &'a Vec<String>) -> Self {
+        Value::Tags(value.len())
+    }
+}
+impl<'a> From<&'a Mutex<u8>> for Value<'a> {
+    fn from(value: &'a Mutex<u8>) -> Self {
+        Value::Locked(value)
+    }
+}
+
+fn document() -> Document {
+    Document {
+        title: String::from("readme"),
+        tags: vec![String::from("docs")],
+        lock: Mutex::new(3),
+        cache: Vec::new(),
+    }
+}
+
+#[test]
+fn iter_fields() {
+    let document = document();
+    let mut fields = document.iter_fields();
+    assert_eq!(fields.len(), 3);
+    match fields.next_back() {
+        Some(DocumentFieldEnumRef::Lock(lock)) => assert_eq!(*lock.lock().unwrap(), 3),
+        other => panic!("expected the lock, got {other:?}"),
+    }
+    match fields.next() {
+        Some(DocumentFieldEnumRef::Title(title)) => assert_eq!(title, "readme"),
+        other => panic!("expected the title, got {other:?}"),
+    }
+    assert_eq!(fields.len(), 1);
+    assert_eq!(document.get_fields_enums_ref().len(), 3);
+
+    let pair = Pair(String::from("left"), 2);
+    assert_eq!(
+        pair.iter_fields().rev().collect::<Vec<_>>(),
+        vec![
+            PairFieldEnumRef::Field1(&2),
+            PairFieldEnumRef::Field0(&pair.0)
+        ]
+    );
+    assert_eq!(Unit.iter_fields().len(), 0);
+}
+
+#[test]
+fn enums_ref() {
+    let document = document();
+    let enums = document.gets_enums_ref();
+    assert_eq!(enums.len(), 3);
+    assert!(matches!(enums[0], DocumentEnumRef::String(title) if title == "readme"));
+
+    let pair = Pair(7u64, 2);
+    assert_eq!(
+        pair.gets_enums_ref(),
+        vec![PairEnumRef::T(&7), PairEnumRef::U8(&2)]
+    );
+}
+
+#[test]
+fn enum_variants() {
+    let (from, to) = (String::from("a"), String::from("b"));
+    let moved = Event::Moved(from.clone(), to.clone());
+    assert_eq!(
+        moved.iter_fields().collect::<Vec<_>>(),
+        vec![
+            EventFieldEnumRef::MovedField0(&from),
+            EventFieldEnumRef::MovedField1(&to),
+        ]
+    );
+    assert_eq!(moved.gets_enums_ref().len(), 2);
+    assert_eq!(Event::Closed.iter_fields().len(), 0);
+}
+
+#[test]
+fn values_ref() {
+    let document = document();
+    let values = document.values_ref::<Value>();
+    assert!(matches!(values[0], Value::Text("readme")));
+    assert!(matches!(values[1], Value::Tags(1)));
+    assert!(matches!(values[2], Value::Locked(_)));
+}
+
+#[test]
+fn enum_ref_conversions() {
+    let text = String::from("todo");
+    assert_eq!(NoteEnumRef::from(&text), NoteEnumRef::String(&text));
+    assert_eq!(NoteEnumRef::from(&3), NoteEnumRef::U32(&3));
+
+    let note = Note {
+        text: String::from("fixme"),
+        line: 12,
+    };
+    let owned = note
+        .gets_enums_ref()
+        .iter()
+        .map(NoteEnumRef::cloned)
+        .collect::<Vec<NoteEnum>>();
+    assert_eq!(owned, note.gets_enums());
+
+    // a bare type parameter next to other types has no `From` impl, but can still be cloned
+    let pair = Pair(String::from("left"), 2);
+    let sides = pair.gets_enums_ref();
+    assert_eq!(sides[0].cloned::<Side>().0, "left");
+    assert_eq!(sides[1].cloned::<Side>().0, "2");
+}
+
+pub struct Side(String);
+impl From<String> for Side {
+    fn from(value: String) -> Self {
+        Side(value)
+    }
+}
+impl From<u8> for Side {
+    fn from(value: u8) -> Self {
+        Side(value.to_string())
+    }
+}
+
+#[test]
+fn never_enum() {
+    assert!(NeverFieldEnumRef::get_variants().is_empty());
+}
@@ -0,0 +1,144 @@
+#![allow(unused)]
+use struct_tools_derive::{StructEnum, StructFieldEnum, StructIterTools};
+
+/// Neither `Clone` nor `Debug`, like a file handle
+pub struct Handle(u8);
+
+#[derive(StructIterTools, StructEnum, StructFieldEnum)]
+#[struct_tools(values, into)]
+pub struct Upload {
+    name: String,
+    handle: Handle,
+    size: Box<u64>,
+    #[struct_tools(skip)]
+    retries: u8,
+}
+
+#[derive(StructEnum, StructFieldEnum)]
+#[struct_tools(into, enum_derive(Debug, PartialEq))]
+pub enum Message {
+    Text { body: String },
+    Pair(u8, String),
+    Empty,
+}
+
+#[derive(StructEnum, StructFieldEnum)]
+#[struct_tools(into, enum_derive(Debug, PartialEq))]
+pub struct Wrapper<T>(Vec<T>, u8);
+
+// can't be taken apart, so it only gets the methods that don't need `into`
+#[derive(StructIterTools, StructEnum, StructFieldEnum)]
+#[struct_tools(values, enum_derive(Debug, PartialEq))]
+pub struct Guard {
+    name: String,
+    level: u8,
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {}
+}
+
+pub enum Value {
+    Name(String),
+    Handle(Handle),
+    Size(u64),
+}
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::Name(value)
+    }
+}
+impl From<Handle> for Value {
+    fn from(value: Handle) -> Self {
+        Value::Handle(value)
+    }
+}
+impl From<Box<u64>> for Value {
+    fn from(value: Box<u64>) -> Self {
+        Value::Size(*value)
+    }
+}
+
+fn upload() -> Upload {
+    Upload {
+        name: String::from("data.csv"),
+        handle: Handle(3),
+        size: Box::new(1024),
+        retries: 0,
+    }
+}
+
+#[test]
+fn into_values() {
+    let values = upload().into_values::<Value>();
+    assert!(matches!(&values[..], [
+        Value::Name(name),
+        Value::Handle(Handle(3)),
+        Value::Size(1024),
+    ] if name == "data.csv"));
+}
+
+#[test]
+fn into_enums() {
+    let enums = upload().into_enums();
+    assert_eq!(enums.len(), 3);
+    assert!(matches!(&enums[1], UploadEnum::Handle(Handle(3))));
+
+    assert_eq!(
+        Message::Pair(1, String::from("one")).into_enums(),
+        vec![MessageEnum::U8(1), MessageEnum::String(String::from("one"))]
+    );
+    assert!(Message::Empty.into_enums().is_empty());
+    assert_eq!(
+        Wrapper(vec![String::from("inner")], 2).into_enums(),
+        vec![
+            WrapperEnum::VecT(vec![String::from("inner")]),
+            WrapperEnum::U8(2)
+        ]
+    );
+}
+
+#[test]
+fn into_fields_enums() {
+    let fields = upload().into_fields_enums();
+    assert!(matches!(&fields[0], UploadFieldEnum::Name(name) if name == "data.csv"));
+    assert!(matches!(&fields[2], UploadFieldEnum::Size(size) if **size == 1024));
+
+    assert_eq!(
+        Message::Text {
+            body: String::from("hi")
+        }
+        .into_fields_enums(),
+        vec![MessageFieldEnum::TextBody(String::from("hi"))]
+    );
+    assert_eq!(
+        Wrapper(vec![7u64], 2).into_fields_enums(),
+        vec![
+            WrapperFieldEnum::Field0(vec![7]),
+            WrapperFieldEnum::Field1(2)
+        ]
+    );
+}
+
+#[test]
+fn drop_without_into() {
+    let guard = Guard {
+        name: String::from("lock"),
+        level: 1,
+    };
+    assert_eq!(
+        guard.gets_enums(),
+        vec![GuardEnum::String(String::from("lock")), GuardEnum::U8(1)]
+    );
+    assert_eq!(
+        guard.get_fields_enums(),
+        vec![
+            GuardFieldEnum::Name(String::from("lock")),
+            GuardFieldEnum::Level(1)
+        ]
+    );
+    assert_eq!(
+        guard.values::<GuardEnum>(),
+        vec![GuardEnum::String(String::from("lock")), GuardEnum::U8(1)]
+    );
+}
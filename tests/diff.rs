@@ -0,0 +1,84 @@
+#![allow(unused)]
+use std::sync::Mutex;
+use struct_tools_derive::StructFieldEnum;
+
+#[derive(Debug, Default, Clone, StructFieldEnum)]
+#[struct_tools(enum_derive(Debug, Clone, PartialEq))]
+pub struct Account {
+    name: String,
+    balance: i64,
+    tags: Vec<String>,
+    #[struct_tools(diff(skip))]
+    updated_at: u64,
+    #[struct_tools(skip)]
+    cache: Vec<u8>,
+}
+
+// `Mutex` is neither `Clone` nor `PartialEq`, so it can't be compared
+#[derive(StructFieldEnum)]
+#[struct_tools(field_change = "SessionDiff")]
+pub struct Session {
+    user: String,
+    #[struct_tools(diff(skip))]
+    lock: Mutex<u8>,
+}
+
+#[derive(Clone, StructFieldEnum)]
+#[struct_tools(enum_derive(Debug, PartialEq))]
+pub struct Pair<T>(T, u8);
+
+#[test]
+fn diff() {
+    let old = Account {
+        name: String::from("Ada"),
+        balance: 10,
+        updated_at: 1,
+        ..Account::default()
+    };
+    let new = Account {
+        balance: 25,
+        tags: vec![String::from("vip")],
+        updated_at: 2,
+        cache: vec![1],
+        ..old.clone()
+    };
+    assert_eq!(
+        old.diff(&new),
+        vec![
+            AccountFieldChange {
+                field: "Balance",
+                old: AccountFieldEnum::Balance(10),
+                new: AccountFieldEnum::Balance(25),
+            },
+            AccountFieldChange {
+                field: "Tags",
+                old: AccountFieldEnum::Tags(Vec::new()),
+                new: AccountFieldEnum::Tags(vec![String::from("vip")]),
+            },
+        ]
+    );
+    assert!(old.diff(&old).is_empty());
+}
+
+#[test]
+fn skipped_field() {
+    let left = Session {
+        user: String::from("ada"),
+        lock: Mutex::new(0),
+    };
+    let right = Session {
+        user: String::from("bob"),
+        lock: Mutex::new(1),
+    };
+    let changes: Vec<SessionDiff> = left.diff(&right);
+    assert_eq!(changes.len(), 1);
+    assert!(matches!(&changes[0].new, SessionFieldEnum::User(user) if user == "bob"));
+}
+
+#[test]
+fn generic() {
+    let changes = Pair(String::from("a"), 1).diff(&Pair(String::from("b"), 1));
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].field, "Field0");
+    assert_eq!(changes[0].old, PairFieldEnum::Field0(String::from("a")));
+}
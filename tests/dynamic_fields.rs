@@ -0,0 +1,103 @@
+#![allow(unused)]
+use struct_tools_derive::{StructEnum, StructFieldEnum, StructFieldEnumRef};
+
+#[derive(Debug, Default, PartialEq, StructEnum, StructFieldEnum, StructFieldEnumRef)]
+#[struct_tools(
+    rename_all = "camelCase",
+    enum_derive(Debug, PartialEq),
+    ref_enum_derive(Debug, PartialEq)
+)]
+pub struct Column {
+    title: String,
+    #[struct_tools(alias = "width")]
+    width_px: u32,
+    visible: bool,
+    #[struct_tools(skip)]
+    cache: Vec<u8>,
+}
+
+#[derive(StructFieldEnumRef)]
+#[struct_tools(ref_enum_derive(Debug, PartialEq))]
+pub enum Shape {
+    Circle { radius: u32 },
+    Rect(u32, u32),
+}
+
+#[derive(StructFieldEnum)]
+#[struct_tools(field_error = "PairError")]
+pub struct Pair(u8, u16);
+
+#[test]
+fn get_field() {
+    let column = Column {
+        title: String::from("name"),
+        width_px: 80,
+        ..Column::default()
+    };
+    assert_eq!(
+        column.get_field("widthPx"),
+        Some(ColumnFieldEnumRef::WidthPx(&80))
+    );
+    assert_eq!(
+        column.get_field("width"),
+        Some(ColumnFieldEnumRef::WidthPx(&80))
+    );
+    assert_eq!(column.get_field("width_px"), None);
+    assert_eq!(column.get_field("cache"), None);
+    assert_eq!(
+        column.get_field_by_index(0),
+        Some(ColumnFieldEnumRef::Title(&column.title))
+    );
+    assert_eq!(column.get_field_by_index(3), None);
+
+    let rect = Shape::Rect(2, 3);
+    assert_eq!(rect.get_field("1"), Some(ShapeFieldEnumRef::RectField1(&3)));
+    assert_eq!(rect.get_field("radius"), None);
+    assert_eq!(
+        Shape::Circle { radius: 1 }.get_field_by_index(0),
+        Some(ShapeFieldEnumRef::CircleRadius(&1))
+    );
+}
+
+#[test]
+fn set_field() {
+    let mut column = Column::default();
+    column.set_field(ColumnFieldEnum::Title(String::from("name")));
+    column.set_field(ColumnFieldEnum::Visible(true));
+    assert_eq!(column.title, "name");
+    assert!(column.visible);
+
+    let mut pair = Pair(0, 0);
+    pair.set_field(PairFieldEnum::Field1(7));
+    assert_eq!(pair.1, 7);
+}
+
+#[test]
+fn set_field_by_name() {
+    let mut column = Column::default();
+    assert_eq!(
+        column.set_field_by_name("width", ColumnEnum::U32(120)),
+        Ok(())
+    );
+    assert_eq!(column.width_px, 120);
+    assert_eq!(
+        column.set_field_by_name("visible", ColumnEnum::String(String::from("yes"))),
+        Err(ColumnFieldError::WrongType("visible"))
+    );
+    assert_eq!(
+        column.set_field_by_name("height", ColumnEnum::U32(1)),
+        Err(ColumnFieldError::UnknownField(String::from("height")))
+    );
+    assert_eq!(
+        ColumnFieldError::UnknownField(String::from("height")).to_string(),
+        "there is no field named `height`"
+    );
+
+    let mut pair = Pair(0, 0);
+    assert_eq!(pair.set_field_by_name("0", 5u8), Ok(()));
+    assert_eq!(pair.0, 5);
+    assert_eq!(
+        pair.set_field_by_name("1", 70_000u32),
+        Err(PairError::WrongType("1"))
+    );
+}
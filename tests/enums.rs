@@ -0,0 +1,145 @@
+#![allow(unused)]
+use struct_tools_derive::{StructEnum, StructFieldEnum, StructIterTools};
+
+#[derive(Debug, Clone, StructIterTools, StructEnum, StructFieldEnum)]
+#[struct_tools(
+    fields,
+    values,
+    rename_all = "camelCase",
+    enum_derive(Debug, PartialEq)
+)]
+pub enum Shape {
+    Circle {
+        radius: f64,
+        #[struct_tools(skip)]
+        cached_area: f64,
+    },
+    Rect(f64, f64),
+    Labelled {
+        label_text: String,
+        shape: Box<Shape>,
+    },
+    Empty,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Value {
+    F64(f64),
+    String(String),
+    Shape(Box<Shape>),
+}
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Value::F64(value)
+    }
+}
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::String(value)
+    }
+}
+impl From<Box<Shape>> for Value {
+    fn from(value: Box<Shape>) -> Self {
+        Value::Shape(value)
+    }
+}
+impl PartialEq for Shape {
+    fn eq(&self, other: &Self) -> bool {
+        format!("{self:?}") == format!("{other:?}")
+    }
+}
+
+#[derive(StructIterTools, StructEnum)]
+#[struct_tools(fields, values, enum_derive(Debug, PartialEq))]
+pub enum Either<L, R> {
+    Left(L),
+    Right { value: R },
+}
+
+#[derive(StructIterTools)]
+#[struct_tools(fields, values)]
+pub enum Never {}
+
+#[test]
+fn fields() {
+    assert_eq!(
+        Shape::variants_fields(),
+        vec![
+            (String::from("Circle"), vec![String::from("radius")]),
+            (
+                String::from("Rect"),
+                vec![String::from("0"), String::from("1")]
+            ),
+            (
+                String::from("Labelled"),
+                vec![String::from("labelText"), String::from("shape")]
+            ),
+            (String::from("Empty"), vec![]),
+        ]
+    );
+    let circle = Shape::Circle {
+        radius: 1.0,
+        cached_area: 3.0,
+    };
+    assert_eq!(circle.variant_fields(), vec!["radius"]);
+    assert_eq!(Shape::Rect(1.0, 2.0).variant_fields(), vec!["0", "1"]);
+    assert!(Shape::Empty.variant_fields().is_empty());
+    assert!(Never::variants_fields().is_empty());
+}
+
+#[test]
+fn values() {
+    let labelled = Shape::Labelled {
+        label_text: String::from("unit"),
+        shape: Box::new(Shape::Empty),
+    };
+    assert_eq!(
+        labelled.fields_and_values::<Value>(),
+        vec![
+            (
+                String::from("labelText"),
+                Value::String(String::from("unit"))
+            ),
+            (String::from("shape"), Value::Shape(Box::new(Shape::Empty))),
+        ]
+    );
+    assert_eq!(
+        Shape::Rect(1.0, 2.0).values::<Value>(),
+        vec![Value::F64(1.0), Value::F64(2.0)]
+    );
+    assert!(Shape::Empty.values::<Value>().is_empty());
+
+    let either: Either<f64, String> = Either::Right {
+        value: String::from("right"),
+    };
+    assert_eq!(Either::<u8, String>::variants_fields().len(), 2);
+    assert_eq!(either.variant_fields(), vec!["value"]);
+    assert_eq!(
+        either.values::<Value>(),
+        vec![Value::String(String::from("right"))]
+    );
+}
+
+#[test]
+fn value_enums() {
+    let circle = Shape::Circle {
+        radius: 1.0,
+        cached_area: 3.0,
+    };
+    assert_eq!(circle.gets_enums(), vec![ShapeEnum::F64(1.0)]);
+    assert_eq!(
+        circle.get_fields_enums(),
+        vec![ShapeFieldEnum::CircleRadius(1.0)]
+    );
+    assert_eq!(
+        Shape::Rect(1.0, 2.0).get_fields_enums(),
+        vec![
+            ShapeFieldEnum::RectField0(1.0),
+            ShapeFieldEnum::RectField1(2.0)
+        ]
+    );
+    assert!(Shape::Empty.gets_enums().is_empty());
+
+    let left: Either<f64, String> = Either::Left(7.0);
+    assert_eq!(left.gets_enums(), vec![EitherEnum::L(7.0)]);
+}
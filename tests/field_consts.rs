@@ -0,0 +1,44 @@
+#![allow(unused)]
+use struct_tools_derive::StructIterTools;
+
+#[derive(StructIterTools)]
+#[struct_tools(fields, rename_all = "camelCase")]
+pub struct Metric<T> {
+    metric_name: String,
+    #[struct_tools(alias = "amount", field_const = "FIELD_AMOUNT")]
+    count: u64,
+    r#type: T,
+    #[struct_tools(skip)]
+    hidden: bool,
+}
+
+#[derive(StructIterTools)]
+#[struct_tools(fields)]
+pub struct Pair(u8, #[struct_tools(rename = "second")] u8);
+
+const TYPE_INDEX: Option<usize> = Metric::<()>::field_index("type");
+
+#[test]
+fn constants() {
+    assert_eq!(Metric::<()>::FIELD_NAMES, ["metricName", "count", "type"]);
+    assert_eq!(Metric::<()>::FIELD_COUNT, 3);
+    assert_eq!(Metric::<()>::FIELD_METRIC_NAME, "metricName");
+    assert_eq!(Metric::<()>::FIELD_TYPE, "type");
+    assert_eq!(Metric::<()>::FIELD_AMOUNT, "count");
+    assert_eq!(Metric::<()>::fields(), Metric::<()>::FIELD_NAMES);
+
+    assert_eq!(Pair::FIELD_NAMES, ["0", "second"]);
+    assert_eq!(Pair::FIELD_0, "0");
+    assert_eq!(Pair::FIELD_1, "second");
+
+    let labels: [u64; Metric::<()>::FIELD_COUNT] = [0; Metric::<()>::FIELD_COUNT];
+    assert_eq!(labels.len(), 3);
+}
+
+#[test]
+fn const_field_index() {
+    assert_eq!(TYPE_INDEX, Some(2));
+    assert_eq!(Metric::<()>::field_index("amount"), Some(1));
+    assert_eq!(Metric::<()>::field_index("metric_name"), None);
+    assert_eq!(Pair::field_index("second"), Some(1));
+}
@@ -0,0 +1,132 @@
+#![allow(unused)]
+use std::collections::{BTreeMap, HashMap};
+use struct_tools_derive::{StructEnum, StructIterTools};
+
+#[derive(Debug, Clone, PartialEq, StructIterTools, StructEnum)]
+#[struct_tools(
+    fields,
+    values,
+    rename_all = "camelCase",
+    enum_derive(Debug, Clone, PartialEq)
+)]
+pub struct Server {
+    host_name: String,
+    #[struct_tools(alias = "portNumber")]
+    port: u16,
+    tags: Vec<String>,
+    #[struct_tools(skip, builder(default = 3))]
+    retries: u8,
+    #[struct_tools(skip)]
+    cache: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq, StructIterTools, StructEnum)]
+#[struct_tools(values, into, from_values_error = "PairError")]
+pub struct Pair(u8, String);
+
+/// Not `Default`, so it can't be filled in when skipped
+pub struct Handle;
+
+#[derive(StructIterTools)]
+#[struct_tools(fields, values)]
+pub struct Connection {
+    id: u8,
+    #[struct_tools(skip)]
+    handle: Handle,
+}
+
+fn server() -> Server {
+    Server {
+        host_name: String::from("localhost"),
+        port: 80,
+        tags: vec![String::from("web")],
+        retries: 3,
+        cache: Vec::new(),
+    }
+}
+
+#[test]
+fn from_fields_and_values() {
+    let fields_and_values = server().fields_and_values::<ServerEnum>();
+    assert_eq!(
+        Server::from_fields_and_values(fields_and_values),
+        Ok(server())
+    );
+
+    let by_alias = [
+        ("tags", ServerEnum::VecString(Vec::new())),
+        ("portNumber", ServerEnum::U16(8080)),
+        ("hostName", ServerEnum::String(String::from("example.org"))),
+    ];
+    let built = Server::from_fields_and_values(by_alias).unwrap();
+    assert_eq!((built.port, built.retries), (8080, 3));
+}
+
+#[test]
+fn from_fields_and_values_error() {
+    let error = Server::from_fields_and_values([
+        ("port", ServerEnum::U16(1)),
+        ("port", ServerEnum::U16(2)),
+        ("tags", ServerEnum::U16(3)),
+        ("hostname", ServerEnum::String(String::new())),
+    ])
+    .unwrap_err();
+    assert_eq!(
+        error,
+        ServerFromValuesError {
+            missing: vec!["hostName"],
+            duplicated: vec!["port"],
+            unknown: vec![String::from("hostname")],
+            wrong_variant: vec!["tags"],
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        "can't build `Server` from the values, fields missing: hostName; fields duplicated: port; \
+         fields unknown: hostname; fields of the wrong variant: tags"
+    );
+
+    // a field given twice is only reported as duplicated, whatever its values
+    let error = Server::from_fields_and_values([
+        ("tags", ServerEnum::U16(1)),
+        ("tags", ServerEnum::VecString(Vec::new())),
+    ])
+    .unwrap_err();
+    assert_eq!(error.duplicated, vec!["tags"]);
+    assert!(error.wrong_variant.is_empty());
+    assert_eq!(error.missing, vec!["hostName", "port"]);
+}
+
+#[test]
+fn from_values() {
+    let values = Pair(1, String::from("one")).into_values::<PairEnum>();
+    assert_eq!(Pair::from_values(values), Ok(Pair(1, String::from("one"))));
+
+    assert_eq!(
+        Pair::from_values(vec![PairEnum::String(String::new())]),
+        Err(PairError {
+            missing: vec!["1"],
+            wrong_variant: vec!["0"],
+            ..PairError::default()
+        })
+    );
+    let error = Pair::from_values([
+        PairEnum::U8(1),
+        PairEnum::String(String::new()),
+        PairEnum::U8(2),
+    ])
+    .unwrap_err();
+    assert_eq!(error.unknown, vec!["2"]);
+    assert!(!error.is_empty());
+}
+
+#[test]
+fn maps() {
+    let map: HashMap<String, ServerEnum> = server().to_map();
+    assert_eq!(map["port"], ServerEnum::U16(80));
+    assert_eq!(Server::try_from_map(map), Ok(server()));
+
+    let mut map: BTreeMap<String, ServerEnum> = server().to_map();
+    map.remove("tags");
+    assert_eq!(Server::try_from_map(map).unwrap_err().missing, vec!["tags"]);
+}
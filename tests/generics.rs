@@ -0,0 +1,221 @@
+#![allow(unused)]
+use std::fmt::Debug;
+use struct_tools_derive::{
+    StructBuilder, StructEnum, StructEnumMut, StructEnumRef, StructFieldEnum, StructFieldEnumMut,
+    StructIterTools,
+};
+
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    StructIterTools,
+    StructEnum,
+    StructEnumMut,
+    StructFieldEnum,
+    StructFieldEnumMut,
+    StructBuilder,
+)]
+#[struct_tools(
+    fields,
+    values,
+    enum_derive(Debug, Clone, PartialEq),
+    mut_enum_derive(Debug)
+)]
+pub struct Page<T>
+where
+    T: Clone,
+{
+    number: u32,
+    items: Vec<T>,
+}
+
+#[derive(Debug, PartialEq, StructIterTools, StructFieldEnum, StructFieldEnumMut)]
+#[struct_tools(fields, values, enum_derive(Debug, PartialEq))]
+pub struct Table<'a, T: Debug, const N: usize, U = ()> {
+    name: &'a str,
+    rows: [T; N],
+    meta: U,
+}
+
+// `T` could be a `u8` as well, so only the `u8` converts into the enum
+#[derive(StructEnum, StructEnumMut)]
+#[struct_tools(enum_derive(Debug, PartialEq), mut_enum_derive(Debug, PartialEq))]
+pub struct Tagged<T> {
+    value: T,
+    tag: u8,
+}
+
+// `Vec<T>` could be a `Vec<u8>` as well, so only the `Vec<u8>` converts into the enum
+#[derive(StructEnum, StructEnumMut, StructEnumRef)]
+#[struct_tools(
+    enum_derive(Debug, PartialEq),
+    mut_enum_derive(Debug, PartialEq),
+    ref_enum_derive(Debug, PartialEq)
+)]
+pub struct Buffers<T> {
+    items: Vec<T>,
+    raw: Vec<u8>,
+}
+
+mod builder {
+    use struct_tools_derive::StructBuilder;
+
+    #[derive(Debug, PartialEq, StructBuilder)]
+    #[struct_tools(fields)]
+    pub struct Wrapper<'a, T, const N: usize> {
+        pub name: &'a str,
+        pub values: [T; N],
+    }
+}
+
+mod old_builder {
+    use struct_tools_derive::StructBuilderOld;
+
+    #[derive(Debug, PartialEq, StructBuilderOld)]
+    #[struct_tools(fields)]
+    pub struct Wrapper<T: Clone> {
+        pub values: Vec<T>,
+    }
+}
+
+#[test]
+fn generic_fields_and_values() {
+    let page = Page {
+        number: 1,
+        items: vec!["a".to_owned()],
+    };
+    assert_eq!(Page::<String>::fields(), vec!["number", "items"]);
+    assert_eq!(
+        page.values::<PageEnum<String>>(),
+        vec![PageEnum::U32(1), PageEnum::VecT(vec!["a".to_owned()])]
+    );
+    let items: Result<Vec<String>, PageEnumError> = PageEnum::VecT(vec!["b".to_owned()]).try_into();
+    assert_eq!(items, Ok(vec!["b".to_owned()]));
+}
+
+#[test]
+fn generic_field_enums() {
+    let mut page = Page {
+        number: 1,
+        items: vec![1u8],
+    };
+    assert_eq!(
+        page.get_fields_enums(),
+        vec![PageFieldEnum::Number(1), PageFieldEnum::Items(vec![1])]
+    );
+    for field in page.get_fields_enums_mut() {
+        match field {
+            PageFieldEnumMut::Number(number) => *number += 1,
+            PageFieldEnumMut::Items(items) => items.push(2),
+        }
+    }
+    for value in page.gets_enums_mut() {
+        if let PageEnumMut::U32(number) = value {
+            *number += 1
+        }
+    }
+    assert_eq!(
+        page,
+        Page {
+            number: 3,
+            items: vec![1, 2]
+        }
+    );
+}
+
+#[test]
+fn lifetimes_and_const_generics() {
+    let table = Table {
+        name: "table",
+        rows: [1, 2, 3],
+        meta: (),
+    };
+    assert_eq!(Table::<i32, 3>::fields(), vec!["name", "rows", "meta"]);
+    assert_eq!(
+        table.get_fields_enums(),
+        vec![
+            TableFieldEnum::Name("table"),
+            TableFieldEnum::Rows([1, 2, 3]),
+            TableFieldEnum::Meta(())
+        ]
+    );
+}
+
+#[test]
+fn generic_builder() {
+    let page = PageBuilder::default()
+        .set_number(2)
+        .set_items(vec![1u8])
+        .build();
+    assert_eq!(
+        page,
+        Page {
+            number: 2,
+            items: vec![1]
+        }
+    );
+
+    let wrapper = builder::WrapperBuilder::default()
+        .set_values([1, 2])
+        .set_name("two")
+        .build();
+    assert_eq!(
+        wrapper,
+        builder::Wrapper {
+            name: "two",
+            values: [1, 2]
+        }
+    );
+
+    let wrapper = old_builder::WrapperBuilder::default()
+        .set_values(vec![1])
+        .build();
+    assert_eq!(wrapper.unwrap(), old_builder::Wrapper { values: vec![1] });
+}
+
+#[test]
+fn bare_param_next_to_other_types() {
+    assert_eq!(TaggedEnum::<String>::from(3), TaggedEnum::U8(3));
+    let mut tag = 4;
+    assert_eq!(
+        TaggedEnumMut::<String>::from(&mut tag),
+        TaggedEnumMut::U8(&mut 4)
+    );
+    let tagged = Tagged {
+        value: "value".to_owned(),
+        tag: 5,
+    };
+    assert_eq!(
+        tagged.gets_enums(),
+        vec![TaggedEnum::T("value".to_owned()), TaggedEnum::U8(5)]
+    );
+}
+
+#[test]
+fn generic_type_next_to_one_it_could_be() {
+    assert_eq!(
+        BuffersEnum::<String>::from(vec![1u8]),
+        BuffersEnum::VecU8(vec![1])
+    );
+    let raw: Result<Vec<u8>, _> = BuffersEnum::<String>::VecU8(vec![2]).try_into();
+    assert_eq!(raw, Ok(vec![2]));
+    let mut raw = vec![3u8];
+    assert_eq!(
+        BuffersEnumMut::<String>::from(&mut raw),
+        BuffersEnumMut::VecU8(&mut vec![3])
+    );
+    let buffers = Buffers {
+        items: vec!["item".to_owned()],
+        raw: vec![4],
+    };
+    assert_eq!(
+        buffers.gets_enums(),
+        vec![
+            BuffersEnum::VecT(vec!["item".to_owned()]),
+            BuffersEnum::VecU8(vec![4])
+        ]
+    );
+    assert_eq!(buffers.gets_enums_ref()[1], BuffersEnumRef::VecU8(&vec![4]));
+}
@@ -0,0 +1,43 @@
+#![allow(unused, deprecated)]
+use struct_tools_derive::{
+    StructBuilder, StructEnum, StructEnumMut, StructFieldEnum, StructFieldEnumMut, StructIterTools,
+};
+
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    StructIterTools,
+    StructEnum,
+    StructEnumMut,
+    StructFieldEnum,
+    StructFieldEnumMut,
+    StructBuilder,
+)]
+#[StructFields]
+#[StructValues]
+#[EnumDerive(Debug, PartialEq)]
+#[MutEnumDerive(Debug)]
+#[BuilderDerive(Debug)]
+pub struct Book {
+    title: String,
+    #[builder_default(0)]
+    pages: u64,
+}
+
+#[test]
+fn legacy_attributes_still_work() {
+    let book = BookBuilder::default().set_title("Title".to_owned()).build();
+    assert_eq!(Book::fields(), vec!["title", "pages"]);
+    assert_eq!(
+        book.values::<BookEnum>(),
+        vec![BookEnum::String("Title".to_owned()), BookEnum::U64(0)]
+    );
+    assert_eq!(
+        book.get_fields_enums(),
+        vec![
+            BookFieldEnum::Title("Title".to_owned()),
+            BookFieldEnum::Pages(0)
+        ]
+    );
+}
@@ -0,0 +1,156 @@
+#![allow(unused)]
+use struct_tools_derive::{
+    StructBuilder, StructEnum, StructEnumMut, StructFieldEnum, StructFieldEnumMut,
+};
+
+// types of our own, using the names the derives would generate by default
+pub struct BookEnum;
+pub struct BookEnumMut;
+pub struct BookFieldEnum;
+pub struct BookFieldEnumMut;
+pub struct BookBuilder;
+pub struct BookEnumError;
+pub struct BookEnumMutError;
+
+#[derive(
+    Debug, PartialEq, StructEnum, StructEnumMut, StructFieldEnum, StructFieldEnumMut, StructBuilder,
+)]
+#[struct_tools(fields, enum_derive(Debug, PartialEq), mut_enum_derive(Debug))]
+#[struct_tools(
+    enum = "BookValue",
+    enum_mut = "BookValueMut",
+    enum_error = "NotABookValue",
+    enum_mut_error = "NotABookValueMut",
+    field_enum = "BookChange",
+    field_enum_mut = "BookChangeMut",
+    builder = "BookDraft",
+    enum_method = "as_values",
+    enum_mut_method = "as_values_mut",
+    field_enum_method = "changes",
+    field_enum_mut_method = "changes_mut"
+)]
+pub struct Book {
+    title: String,
+    pages: u64,
+}
+
+mod old_builder {
+    use struct_tools_derive::StructBuilderOld;
+
+    #[derive(Debug, PartialEq, StructBuilderOld)]
+    #[struct_tools(fields, builder = "PageDraft", builder_error = "MissingPageField")]
+    pub struct Page {
+        pub number: u32,
+    }
+}
+
+mod raw {
+    use struct_tools_derive::{StructBuilder, StructEnum, StructFieldEnum, StructIterTools};
+
+    // a raw identifier, the generated names are built from `Shelf`
+    #[derive(Debug, PartialEq, StructIterTools, StructEnum, StructFieldEnum, StructBuilder)]
+    #[struct_tools(fields, values, enum_derive(Debug, PartialEq))]
+    pub struct r#Shelf {
+        pub label: String,
+        pub slots: u8,
+    }
+}
+
+fn book() -> Book {
+    BookDraft::default()
+        .set_title("Title".to_owned())
+        .set_pages(100)
+        .build()
+}
+
+#[test]
+fn renamed_enums() {
+    assert_eq!(
+        book().as_values(),
+        vec![BookValue::String("Title".to_owned()), BookValue::U64(100)]
+    );
+    assert_eq!(
+        book().changes(),
+        vec![
+            BookChange::Title("Title".to_owned()),
+            BookChange::Pages(100)
+        ]
+    );
+    assert_eq!(BookChange::get_variants(), vec!["Title", "Pages"]);
+}
+
+#[test]
+fn renamed_mut_enums() {
+    let mut book = book();
+    for value in book.as_values_mut() {
+        if let BookValueMut::U64(pages) = value {
+            *pages += 1;
+        }
+    }
+    for change in book.changes_mut() {
+        if let BookChangeMut::Title(title) = change {
+            title.push('!');
+        }
+    }
+    assert_eq!(
+        book,
+        Book {
+            title: "Title!".to_owned(),
+            pages: 101
+        }
+    );
+}
+
+#[test]
+fn renamed_enum_error() {
+    assert_eq!(
+        u64::try_from(BookValue::String("Title".to_owned())),
+        Err(NotABookValue {
+            expected: "U64",
+            actual: Some("String"),
+        })
+    );
+    let mut title = "Title".to_owned();
+    let error: NotABookValueMut =
+        <&mut u64>::try_from(BookValueMut::String(&mut title)).unwrap_err();
+    assert_eq!(error.expected, "U64");
+}
+
+#[test]
+fn renamed_old_builder() {
+    let missing: Vec<old_builder::MissingPageField> =
+        old_builder::PageDraft::default().build().unwrap_err();
+    assert_eq!(missing.len(), 1);
+    assert_eq!(
+        old_builder::PageDraft::default()
+            .set_number(3)
+            .build()
+            .unwrap(),
+        old_builder::Page { number: 3 }
+    );
+}
+
+#[test]
+fn raw_struct_name() {
+    let shelf = raw::ShelfBuilder::default()
+        .set_label("A".to_owned())
+        .set_slots(0)
+        .build();
+    assert_eq!(
+        shelf.values::<raw::ShelfEnum>(),
+        vec![
+            raw::ShelfEnum::String("A".to_owned()),
+            raw::ShelfEnum::U8(0)
+        ]
+    );
+    assert_eq!(
+        shelf.get_fields_enums(),
+        vec![
+            raw::ShelfFieldEnum::Label("A".to_owned()),
+            raw::ShelfFieldEnum::Slots(0)
+        ]
+    );
+    let error: raw::ShelfFromValuesError =
+        raw::Shelf::from_values(Vec::<raw::ShelfEnum>::new()).unwrap_err();
+    assert_eq!(error.missing, vec!["label", "slots"]);
+}
@@ -0,0 +1,105 @@
+#![allow(unused)]
+use struct_tools_derive::{StructFieldEnum, StructPatch};
+
+#[derive(Debug, Default, PartialEq, StructFieldEnum, StructPatch)]
+#[struct_tools(patch_derive(Debug, Clone, PartialEq))]
+pub struct Config {
+    host: String,
+    port: u16,
+    verbose: bool,
+    #[struct_tools(skip)]
+    cache: Vec<u8>,
+}
+
+#[derive(StructFieldEnum, StructPatch)]
+#[struct_tools(field_enum = "PairField", patch = "PairUpdate")]
+pub struct Pair<T>(T, u8);
+
+#[derive(StructFieldEnum, StructPatch)]
+pub struct Unit;
+
+#[test]
+fn apply_patch() {
+    let mut config = Config {
+        host: String::from("localhost"),
+        port: 80,
+        cache: vec![1],
+        ..Config::default()
+    };
+    config.apply_patch(ConfigPatch {
+        port: Some(8080),
+        verbose: Some(true),
+        ..ConfigPatch::default()
+    });
+    assert_eq!(
+        config,
+        Config {
+            host: String::from("localhost"),
+            port: 8080,
+            verbose: true,
+            cache: vec![1],
+        }
+    );
+
+    let mut pair = Pair(String::from("left"), 1);
+    pair.apply_patch(PairUpdate {
+        _0: Some(String::from("right")),
+        _1: None,
+    });
+    assert_eq!((pair.0.as_str(), pair.1), ("right", 1));
+}
+
+#[test]
+fn is_empty() {
+    assert!(ConfigPatch::default().is_empty());
+    assert!(!ConfigPatch {
+        verbose: Some(false),
+        ..ConfigPatch::default()
+    }
+    .is_empty());
+    assert!(UnitPatch::default().is_empty());
+}
+
+#[test]
+fn merge() {
+    let defaults = ConfigPatch {
+        host: Some(String::from("localhost")),
+        port: Some(80),
+        verbose: None,
+    };
+    let user = ConfigPatch {
+        port: Some(8080),
+        ..ConfigPatch::default()
+    };
+    assert_eq!(
+        defaults.clone().merge(user.clone()),
+        ConfigPatch {
+            host: Some(String::from("localhost")),
+            port: Some(8080),
+            verbose: None,
+        }
+    );
+    assert_eq!(user.merge(defaults.clone()), defaults);
+}
+
+#[test]
+fn from_iter() {
+    let patch: ConfigPatch = vec![
+        ConfigFieldEnum::Port(1),
+        ConfigFieldEnum::Verbose(true),
+        ConfigFieldEnum::Port(2),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(
+        patch,
+        ConfigPatch {
+            host: None,
+            port: Some(2),
+            verbose: Some(true),
+        }
+    );
+
+    let patch: PairUpdate<u64> = [PairField::Field0(7)].into_iter().collect();
+    assert_eq!((patch._0, patch._1), (Some(7), None));
+}
@@ -0,0 +1,75 @@
+#![allow(unused)]
+use struct_tools_derive::StructIterTools;
+
+#[derive(Default, StructIterTools)]
+#[struct_tools(fields, values, rename_all = "camelCase")]
+pub struct Profile {
+    user_id: u64,
+    #[struct_tools(rename = "name", alias = "displayName", alias = "display_name")]
+    display_name: String,
+    r#type: String,
+}
+
+#[derive(StructIterTools)]
+#[struct_tools(fields, rename_all = "kebab-case")]
+pub struct Kebab {
+    created_at: u64,
+    last_seen_at: u64,
+}
+
+#[derive(StructIterTools)]
+#[struct_tools(fields, rename_all = "SCREAMING_SNAKE_CASE")]
+pub struct Screaming {
+    created_at: u64,
+}
+
+#[derive(StructIterTools)]
+#[struct_tools(fields, rename_all = "PascalCase")]
+pub struct Pair(u8, #[struct_tools(rename = "second")] u8);
+
+#[derive(Debug, PartialEq)]
+pub enum Value {
+    U64(u64),
+    String(String),
+}
+impl From<u64> for Value {
+    fn from(value: u64) -> Self {
+        Value::U64(value)
+    }
+}
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::String(value)
+    }
+}
+
+#[test]
+fn rename_all() {
+    assert_eq!(Profile::fields(), vec!["userId", "name", "type"]);
+    assert_eq!(Kebab::fields(), vec!["created-at", "last-seen-at"]);
+    assert_eq!(Screaming::fields(), vec!["CREATED_AT"]);
+    assert_eq!(Pair::fields(), vec!["0", "second"]);
+}
+
+#[test]
+fn renamed_fields_and_values() {
+    assert_eq!(
+        Profile::default().fields_and_values::<Value>(),
+        vec![
+            ("userId".to_owned(), Value::U64(0)),
+            ("name".to_owned(), Value::String(String::new())),
+            ("type".to_owned(), Value::String(String::new())),
+        ]
+    );
+}
+
+#[test]
+fn field_index_accepts_aliases() {
+    assert_eq!(Profile::field_index("userId"), Some(0));
+    assert_eq!(Profile::field_index("name"), Some(1));
+    assert_eq!(Profile::field_index("displayName"), Some(1));
+    assert_eq!(Profile::field_index("display_name"), Some(1));
+    assert_eq!(Profile::field_index("type"), Some(2));
+    assert_eq!(Profile::field_index("user_id"), None);
+    assert_eq!(Pair::field_index("second"), Some(1));
+}
@@ -0,0 +1,170 @@
+#![allow(unused)]
+use std::marker::PhantomData;
+use struct_tools_derive::{
+    StructBuilder, StructEnum, StructEnumMut, StructFieldEnum, StructFieldEnumMut, StructIterTools,
+};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Meters;
+
+#[derive(
+    Debug,
+    PartialEq,
+    StructIterTools,
+    StructEnum,
+    StructEnumMut,
+    StructFieldEnum,
+    StructFieldEnumMut,
+    StructBuilder,
+)]
+#[struct_tools(fields, values, enum_derive(Debug, PartialEq), mut_enum_derive(Debug))]
+pub struct Distance<U> {
+    amount: f64,
+    label: String,
+    #[struct_tools(skip)]
+    unit: PhantomData<U>,
+    #[struct_tools(skip, builder(default = Some(0.0)))]
+    cache: Option<f64>,
+    #[struct_tools(skip(enum))]
+    hidden_from_enums: u8,
+    #[struct_tools(skip(iter, builder))]
+    hidden_from_iter: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Value {
+    F64(f64),
+    String(String),
+    U8(u8),
+}
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Value::F64(value)
+    }
+}
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::String(value)
+    }
+}
+impl From<u8> for Value {
+    fn from(value: u8) -> Self {
+        Value::U8(value)
+    }
+}
+
+fn distance() -> Distance<Meters> {
+    Distance {
+        amount: 1.5,
+        label: "one and a half".to_owned(),
+        unit: PhantomData,
+        cache: None,
+        hidden_from_enums: 3,
+        hidden_from_iter: vec![1],
+    }
+}
+
+mod tuple {
+    use struct_tools_derive::{StructBuilder, StructIterTools};
+
+    #[derive(Debug, PartialEq, StructIterTools, StructBuilder)]
+    #[struct_tools(fields)]
+    pub struct Pair(pub u8, #[struct_tools(skip)] pub String, pub u8);
+}
+
+mod old_builder {
+    use std::marker::PhantomData;
+    use struct_tools_derive::StructBuilderOld;
+
+    #[derive(Debug, PartialEq, StructBuilderOld)]
+    #[struct_tools(fields)]
+    pub struct Tagged<T> {
+        pub id: u64,
+        #[struct_tools(skip)]
+        pub tag: PhantomData<T>,
+    }
+}
+
+#[test]
+fn skipped_fields_are_not_listed() {
+    assert_eq!(
+        Distance::<Meters>::fields(),
+        vec!["amount", "label", "hidden_from_enums"]
+    );
+    assert_eq!(tuple::Pair::fields(), vec!["0", "2"]);
+}
+
+#[test]
+fn skipped_fields_have_no_values() {
+    // `Value` has no `From<PhantomData<Meters>>`, `From<Option<f64>>` or `From<Vec<u8>>`
+    assert_eq!(
+        distance().values::<Value>(),
+        vec![
+            Value::F64(1.5),
+            Value::String("one and a half".to_owned()),
+            Value::U8(3)
+        ]
+    );
+}
+
+#[test]
+fn skipped_fields_have_no_variants() {
+    assert_eq!(
+        DistanceFieldEnum::get_variants(),
+        vec!["Amount", "Label", "HiddenFromIter"]
+    );
+    assert_eq!(
+        distance().get_fields_enums(),
+        vec![
+            DistanceFieldEnum::Amount(1.5),
+            DistanceFieldEnum::Label("one and a half".to_owned()),
+            DistanceFieldEnum::HiddenFromIter(vec![1]),
+        ]
+    );
+    assert_eq!(
+        distance().gets_enums(),
+        vec![
+            DistanceEnum::F64(1.5),
+            DistanceEnum::String("one and a half".to_owned()),
+            DistanceEnum::VecU8(vec![1]),
+        ]
+    );
+    let mut distance = distance();
+    assert_eq!(distance.get_fields_enums_mut().len(), 3);
+    assert_eq!(distance.gets_enums_mut().len(), 3);
+}
+
+#[test]
+fn skipped_fields_are_filled_by_the_builder() {
+    let built: Distance<Meters> = DistanceBuilder::default()
+        .set_amount(2.0)
+        .set_label("two".to_owned())
+        .set_hidden_from_enums(4)
+        .build();
+    assert_eq!(
+        built,
+        Distance {
+            amount: 2.0,
+            label: "two".to_owned(),
+            unit: PhantomData,
+            cache: Some(0.0),
+            hidden_from_enums: 4,
+            hidden_from_iter: Vec::new(),
+        }
+    );
+
+    let pair = tuple::PairBuilder::default().set_0(1).set_2(2).build();
+    assert_eq!(pair, tuple::Pair(1, String::new(), 2));
+
+    let tagged = old_builder::TaggedBuilder::<String>::default()
+        .set_id(7)
+        .build()
+        .unwrap();
+    assert_eq!(
+        tagged,
+        old_builder::Tagged {
+            id: 7,
+            tag: PhantomData
+        }
+    );
+}
@@ -0,0 +1,111 @@
+#![allow(unused)]
+use struct_tools_derive::{
+    StructBuilder, StructEnum, StructEnumMut, StructFieldEnum, StructFieldEnumMut, StructIterTools,
+};
+
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    StructIterTools,
+    StructEnum,
+    StructEnumMut,
+    StructFieldEnum,
+    StructFieldEnumMut,
+    StructBuilder,
+)]
+#[struct_tools(fields, values, enum_derive(Debug, PartialEq))]
+pub struct Point(f32, #[struct_tools(builder(default = 0.0))] f32, String);
+
+mod unit {
+    use struct_tools_derive::{
+        StructBuilder, StructEnum, StructEnumMut, StructFieldEnum, StructFieldEnumMut,
+        StructIterTools,
+    };
+
+    #[derive(
+        Debug,
+        PartialEq,
+        StructIterTools,
+        StructEnum,
+        StructEnumMut,
+        StructFieldEnum,
+        StructFieldEnumMut,
+        StructBuilder,
+    )]
+    #[struct_tools(fields, values, enum_derive(Debug))]
+    pub struct Unit;
+}
+
+mod old_builder {
+    use struct_tools_derive::StructBuilderOld;
+
+    #[derive(Debug, PartialEq, StructBuilderOld)]
+    #[struct_tools(fields)]
+    pub struct Pair(pub u8, #[struct_tools(builder(default = 2))] pub u8);
+}
+
+#[test]
+fn tuple_fields() {
+    assert_eq!(Point::fields(), vec!["0", "1", "2"]);
+    assert_eq!(
+        PointFieldEnum::get_variants(),
+        vec!["Field0", "Field1", "Field2"]
+    );
+}
+
+#[test]
+fn tuple_values() {
+    let point = Point(1.0, 2.0, "p".to_owned());
+    assert_eq!(
+        point.values::<PointEnum>(),
+        vec![
+            PointEnum::F32(1.0),
+            PointEnum::F32(2.0),
+            PointEnum::String("p".to_owned())
+        ]
+    );
+    assert_eq!(
+        point.get_fields_enums(),
+        vec![
+            PointFieldEnum::Field0(1.0),
+            PointFieldEnum::Field1(2.0),
+            PointFieldEnum::Field2("p".to_owned())
+        ]
+    );
+}
+
+#[test]
+fn tuple_mut() {
+    let mut point = Point::default();
+    for field in point.get_fields_enums_mut() {
+        match field {
+            PointFieldEnumMut::Field0(x) => *x = 1.0,
+            PointFieldEnumMut::Field1(y) => *y = 2.0,
+            PointFieldEnumMut::Field2(name) => name.push('p'),
+        }
+    }
+    assert_eq!(point, Point(1.0, 2.0, "p".to_owned()));
+}
+
+#[test]
+fn tuple_builder() {
+    let point = PointBuilder::default()
+        .set_0(1.0)
+        .set_2("p".to_owned())
+        .build();
+    assert_eq!(point, Point(1.0, 0.0, "p".to_owned()));
+
+    let pair = old_builder::PairBuilder::default().set_0(1).build();
+    assert_eq!(pair.unwrap(), old_builder::Pair(1, 2));
+}
+
+#[test]
+fn unit_struct() {
+    assert!(unit::Unit::fields().is_empty());
+    assert!(unit::Unit.values::<unit::UnitEnum>().is_empty());
+    assert!(unit::Unit.get_fields_enums().is_empty());
+    assert!(unit::UnitFieldEnum::get_variants().is_empty());
+    assert_eq!(unit::UnitBuilder::default().build(), unit::Unit);
+}
@@ -0,0 +1,76 @@
+#![allow(unused)]
+use std::collections::HashMap;
+use struct_tools_derive::{StructEnum, StructEnumRef};
+
+mod metric {
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Length(pub f64);
+}
+mod imperial {
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Length(pub f64);
+}
+
+#[derive(StructEnum)]
+#[struct_tools(enum_derive(Debug, Clone, PartialEq))]
+pub struct Shapes<const N: usize> {
+    pair: (u8, u8),
+    unit: (),
+    bytes: [u8; 4],
+    points: [u16; N],
+    lookup: HashMap<String, Vec<(u8, bool)>>,
+    #[struct_tools(variant = "Width")]
+    metric: metric::Length,
+    imperial: imperial::Length,
+    nested: Option<Option<std::string::String>>,
+}
+
+#[derive(StructEnumRef)]
+pub struct Borrowed<'a> {
+    name: &'a str,
+    data: &'a mut [u8],
+    handler: Box<dyn Fn(u8) -> u8>,
+    callback: fn(u8) -> bool,
+}
+
+#[test]
+fn generated_names() {
+    let shapes = Shapes::<2> {
+        pair: (1, 2),
+        unit: (),
+        bytes: [0; 4],
+        points: [3; 2],
+        lookup: HashMap::new(),
+        metric: metric::Length(1.0),
+        imperial: imperial::Length(2.0),
+        nested: None,
+    };
+    let enums = shapes.gets_enums();
+    assert_eq!(enums[0], ShapesEnum::TupleU8U8((1, 2)));
+    assert_eq!(enums[1], ShapesEnum::Unit(()));
+    assert_eq!(enums[2], ShapesEnum::Array4U8([0; 4]));
+    assert_eq!(enums[3], ShapesEnum::ArrayNU16([3; 2]));
+    assert_eq!(
+        enums[4],
+        ShapesEnum::HashMapStringVecTupleU8Bool(HashMap::new())
+    );
+    assert_eq!(enums[5], ShapesEnum::Width(metric::Length(1.0)));
+    assert_eq!(enums[6], ShapesEnum::Length(imperial::Length(2.0)));
+    assert_eq!(enums[7], ShapesEnum::OptionOptionString(None));
+}
+
+#[test]
+fn reference_names() {
+    let mut data = [1u8];
+    let borrowed = Borrowed {
+        name: "name",
+        data: &mut data,
+        handler: Box::new(|byte| byte),
+        callback: |byte| byte > 1,
+    };
+    let enums = borrowed.gets_enums_ref();
+    assert!(matches!(enums[0], BorrowedEnumRef::RefStr(&"name")));
+    assert!(matches!(enums[1], BorrowedEnumRef::RefMutSliceU8([1])));
+    assert!(matches!(enums[2], BorrowedEnumRef::BoxDynFnU8ToU8(_)));
+    assert!(matches!(enums[3], BorrowedEnumRef::FnU8ToBool(_)));
+}
@@ -0,0 +1,9 @@
+use struct_tools_derive::StructFieldEnum;
+
+#[derive(StructFieldEnum)]
+pub struct Slot {
+    value: u8,
+    value_mut: u8,
+}
+
+fn main() {}
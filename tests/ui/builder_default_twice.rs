@@ -0,0 +1,11 @@
+use struct_tools_derive::StructBuilder;
+
+#[derive(StructBuilder)]
+#[struct_tools(fields)]
+pub struct Book {
+    #[struct_tools(builder(default = 0))]
+    #[struct_tools(builder(default = 1))]
+    pages: u64,
+}
+
+fn main() {}
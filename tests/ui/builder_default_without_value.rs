@@ -0,0 +1,10 @@
+use struct_tools_derive::StructBuilder;
+
+#[derive(StructBuilder)]
+#[struct_tools(fields)]
+pub struct Book {
+    #[struct_tools(builder(default))]
+    pages: u64,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use struct_tools_derive::StructBuilder;
+
+#[derive(StructBuilder)]
+pub struct Book {
+    title: String,
+}
+
+fn main() {}
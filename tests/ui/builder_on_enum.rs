@@ -0,0 +1,9 @@
+use struct_tools_derive::StructBuilder;
+
+#[derive(StructBuilder)]
+#[struct_tools(fields)]
+pub enum Book {
+    Title(String),
+}
+
+fn main() {}
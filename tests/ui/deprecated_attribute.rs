@@ -0,0 +1,11 @@
+#![deny(deprecated)]
+use struct_tools_derive::{StructEnum, StructIterTools};
+
+#[derive(StructIterTools, StructEnum)]
+#[StructFields]
+#[EnumDerive(Debug)]
+pub struct Book {
+    pages: u64,
+}
+
+fn main() {}
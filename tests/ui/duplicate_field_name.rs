@@ -0,0 +1,11 @@
+use struct_tools_derive::StructIterTools;
+
+#[derive(StructIterTools)]
+#[struct_tools(fields)]
+pub struct Book {
+    title: String,
+    #[struct_tools(alias = "title")]
+    subtitle: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use struct_tools_derive::StructEnum;
+
+#[derive(StructEnum)]
+#[struct_tools(enum_derive(Debug, "Clone"))]
+pub struct Book {
+    pages: u64,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use struct_tools_derive::StructIterTools;
+
+#[derive(StructIterTools)]
+#[struct_tools(fields)]
+pub struct Address {
+    city: String,
+}
+
+#[derive(StructIterTools)]
+#[struct_tools(fields)]
+pub struct Customer {
+    name: String,
+    #[struct_tools(flatten)]
+    address: Address,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use struct_tools_derive::StructFieldEnum;
+
+#[derive(StructFieldEnum)]
+pub struct Book {
+    _1: String,
+}
+
+fn main() {}
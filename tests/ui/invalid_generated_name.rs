@@ -0,0 +1,9 @@
+use struct_tools_derive::StructFieldEnum;
+
+#[derive(StructFieldEnum)]
+#[struct_tools(field_enum = "Book Change")]
+pub struct Book {
+    pages: u64,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use struct_tools_derive::StructEnum;
+
+macro_rules! text {
+    () => {
+        String
+    };
+}
+
+#[derive(StructEnum)]
+pub struct Book {
+    title: text!(),
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use struct_tools_derive::StructIterTools;
+
+#[derive(StructIterTools)]
+#[struct_tools(fields)]
+pub union Number {
+    int: u32,
+    float: f32,
+}
+
+fn main() {}
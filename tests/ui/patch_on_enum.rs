@@ -0,0 +1,8 @@
+use struct_tools_derive::StructPatch;
+
+#[derive(StructPatch)]
+pub enum Book {
+    Title(String),
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+mod books {
+    use struct_tools_derive::StructEnum;
+
+    #[derive(StructEnum)]
+    struct Book {
+        pages: u64,
+    }
+}
+
+fn main() {
+    let _ = books::BookEnum::U64(1);
+}
@@ -0,0 +1,24 @@
+use struct_tools_derive::StructEnum;
+
+mod metric {
+    pub struct Length(pub f64);
+}
+mod imperial {
+    pub struct Length(pub f64);
+}
+
+#[derive(StructEnum)]
+pub struct Distance {
+    metric: metric::Length,
+    imperial: imperial::Length,
+}
+
+#[derive(StructEnum)]
+pub struct Pages {
+    #[struct_tools(variant = "Count")]
+    first: u32,
+    #[struct_tools(variant = "Number")]
+    last: u32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use struct_tools_derive::StructIterTools;
+
+#[derive(StructIterTools)]
+#[struct_tools(fields, nope)]
+pub struct Book {
+    pages: u64,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use struct_tools_derive::StructIterTools;
+
+#[derive(StructIterTools)]
+#[struct_tools(fields, rename_all = "Train-Case")]
+pub struct Book {
+    page_count: u64,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use struct_tools_derive::StructIterTools;
+
+#[derive(StructIterTools)]
+#[struct_tools(fields)]
+pub struct Book {
+    #[struct_tools(skip(values))]
+    pages: u64,
+}
+
+fn main() {}
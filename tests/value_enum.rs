@@ -0,0 +1,112 @@
+#![allow(unused)]
+use struct_tools_derive::{StructEnum, StructIterTools};
+
+pub type Id = u64;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    U64(u64),
+    String(String),
+    OptionString(Option<String>),
+    Flag(bool),
+}
+
+// `id` is a `u64` too, whose conversions `pages` already generates
+#[derive(StructIterTools, StructEnum)]
+#[struct_tools(values, value_enum = crate::Value)]
+pub struct Book {
+    #[struct_tools(skip(from))]
+    id: Id,
+    title: String,
+    pages: u64,
+    subtitle: Option<String>,
+}
+
+// shares `Value` with `Book`, so only `bool` is left to convert
+#[derive(StructEnum)]
+#[struct_tools(into, value_enum = "Value")]
+pub struct Shelf {
+    #[struct_tools(skip(from))]
+    label: String,
+    #[struct_tools(variant = "Flag")]
+    full: bool,
+}
+
+pub struct Celsius(pub f64);
+
+impl From<Celsius> for Value {
+    fn from(value: Celsius) -> Self {
+        Value::String(format!("{}°C", value.0))
+    }
+}
+
+// the impl for `Celsius` already exists, and `T` has to be converted by the enum itself
+#[derive(StructEnum)]
+#[struct_tools(into, value_enum = Value)]
+pub struct Reading<T> {
+    #[struct_tools(skip(from))]
+    temperature: Celsius,
+    raw: T,
+}
+
+fn book() -> Book {
+    Book {
+        id: 1,
+        title: String::from("Dune"),
+        pages: 412,
+        subtitle: None,
+    }
+}
+
+#[test]
+fn gets_enums() {
+    assert_eq!(
+        book().gets_enums(),
+        vec![
+            Value::U64(1),
+            Value::String(String::from("Dune")),
+            Value::U64(412),
+            Value::OptionString(None),
+        ]
+    );
+    assert_eq!(book().values::<Value>(), book().gets_enums());
+
+    let shelf = Shelf {
+        label: String::from("A"),
+        full: true,
+    };
+    assert_eq!(
+        shelf.into_enums(),
+        vec![Value::String(String::from("A")), Value::Flag(true)]
+    );
+
+    let reading = Reading {
+        temperature: Celsius(20.5),
+        raw: 7u64,
+    };
+    assert_eq!(
+        reading.into_enums(),
+        vec![Value::String(String::from("20.5°C")), Value::U64(7)]
+    );
+}
+
+#[test]
+fn try_into() {
+    assert_eq!(u64::try_from(Value::U64(412)), Ok(412));
+    assert_eq!(
+        u64::try_from(Value::String(String::new())),
+        Err(BookEnumError {
+            expected: "U64",
+            actual: Some("String"),
+        })
+    );
+    // `Shelf` doesn't hold a `u64`, so it doesn't know the variant
+    assert_eq!(
+        bool::try_from(Value::U64(1)),
+        Err(ShelfEnumError {
+            expected: "Flag",
+            actual: None,
+        })
+    );
+    assert_eq!(<&bool>::try_from(&Value::Flag(true)), Ok(&true));
+}
@@ -0,0 +1,95 @@
+#![allow(unused)]
+#![deny(private_interfaces, private_bounds)]
+use struct_tools_derive::{StructFieldEnum, StructFieldEnumMut};
+
+#[derive(Debug, PartialEq, Clone)]
+struct Secret(u8);
+
+// `Secret` is more private than a `pub` enum holding it would be
+#[derive(StructFieldEnum, StructFieldEnumMut)]
+#[struct_tools(enum_derive(Debug, PartialEq), vis = "pub(crate)")]
+pub struct Holder {
+    secret: Secret,
+}
+
+mod private {
+    use struct_tools_derive::{
+        StructBuilder, StructEnum, StructEnumMut, StructFieldEnum, StructFieldEnumMut,
+    };
+
+    #[derive(
+        Debug,
+        PartialEq,
+        StructEnum,
+        StructEnumMut,
+        StructFieldEnum,
+        StructFieldEnumMut,
+        StructBuilder,
+    )]
+    #[struct_tools(fields, enum_derive(Debug, PartialEq))]
+    struct Hidden {
+        id: u64,
+    }
+
+    pub(super) fn hidden_variants() -> Vec<&'static str> {
+        let hidden = HiddenBuilder::default().set_id(1).build();
+        assert_eq!(hidden.gets_enums(), vec![HiddenEnum::U64(1)]);
+        HiddenFieldEnum::get_variants()
+    }
+}
+
+mod restricted {
+    use struct_tools_derive::{StructBuilder, StructFieldEnum};
+
+    #[derive(StructFieldEnum, StructBuilder)]
+    #[struct_tools(fields, enum_derive(Debug, PartialEq))]
+    pub(super) struct Visible {
+        pub(super) id: u64,
+    }
+}
+
+mod outer {
+    pub(crate) mod shop {
+        use struct_tools_derive::{StructEnum, StructFieldEnum};
+
+        // the field enum falls back to `vis`, the enum has its own
+        #[derive(StructEnum, StructFieldEnum)]
+        #[struct_tools(
+            enum_derive(Debug, PartialEq),
+            vis = "pub(super)",
+            enum_vis = "pub(crate)"
+        )]
+        pub(super) struct Item {
+            pub(super) id: u64,
+        }
+    }
+
+    pub(crate) fn item_variants() -> Vec<&'static str> {
+        shop::ItemFieldEnum::get_variants()
+    }
+}
+
+#[test]
+fn vis_override() {
+    let holder = Holder { secret: Secret(1) };
+    assert_eq!(
+        holder.get_fields_enums(),
+        vec![HolderFieldEnum::Secret(Secret(1))]
+    );
+}
+
+#[test]
+fn inherited_vis() {
+    assert_eq!(private::hidden_variants(), vec!["Id"]);
+    let visible = restricted::VisibleBuilder::default().set_id(2).build();
+    assert_eq!(
+        visible.get_fields_enums(),
+        vec![restricted::VisibleFieldEnum::Id(2)]
+    );
+}
+
+#[test]
+fn item_vis_override() {
+    assert_eq!(outer::shop::ItemEnum::U64(1).into_u64(), Some(1));
+    assert_eq!(outer::item_variants(), vec!["Id"]);
+}